@@ -1,21 +1,94 @@
 use crate::entity::game::Entity;
+use crate::response_body::AddResponse;
+use crate::response_body::BuildDetail;
+use crate::response_body::BuildInfoResponse;
+use crate::response_body::BuildListResponse;
+use crate::response_body::DependencyInfoResponse;
+use crate::response_body::DependencyListResponse;
+use crate::response_body::DistributionInfoResponse;
+use crate::response_body::DistributionListResponse;
+use crate::response_body::GameDependencyDetail;
+use crate::response_body::GameLayerDetail;
+use crate::response_body::GameListResponse;
+use crate::response_body::GameSummary;
+use crate::response_body::GradeLegendResponse;
+use crate::response_body::GradeLetter;
+use crate::response_body::GradeRepeat;
+use crate::response_body::GuideDetail;
+use crate::response_body::GuideInfoResponse;
+use crate::response_body::GuideListResponse;
+use crate::response_body::HardwareInfoResponse;
+use crate::response_body::HardwareListResponse;
 use crate::response_body::InfoResponse;
+use crate::response_body::IssueDetail;
+use crate::response_body::IssueInfoResponse;
+use crate::response_body::IssueListResponse;
+use crate::response_body::LayerInfoResponse;
+use crate::response_body::LayerListResponse;
+use crate::response_body::ReportDetail;
+use crate::response_body::ReportListResponse;
 use crate::response_body::SearchResponse;
+use crate::response_body::StackDetail;
+use crate::response_body::TagInfoResponse;
+use crate::response_body::TagListResponse;
 
+use super::aggregation;
+use super::alias;
+use super::build;
+use super::dependency;
+use super::distribution;
+use super::export;
+use super::external_id;
 use super::game;
+use super::game_dependency;
+use super::game_layer;
+use super::game_stack;
+use super::game_tag;
+use super::grading;
+use super::guide;
+use super::guide_step;
+use super::hardware;
+use super::issue;
+use super::issue_layer;
+use super::layer;
+use super::markdown;
+use super::report;
+use super::report_layer;
 use super::response_body::BasicResponse;
 use super::response_code::ResponseCode;
 use super::sonic;
+use super::tag;
+use super::tester;
+use actix_identity::Identity;
+use actix_web::web::Data;
 use actix_web::web::Json;
 use actix_web::web::Query;
 use actix_web::{get, post, HttpResponse};
 use config::Config;
 use futures::future::join_all;
 use lazy_static::lazy_static;
+use sea_orm::sea_query::Expr;
+use sea_orm::sea_query::LikeExpr;
+use sea_orm::sea_query::OnConflict;
 use sea_orm::ActiveModelTrait;
-use sea_orm::Database;
+use sea_orm::ActiveValue;
+use sea_orm::ColumnTrait;
+use sea_orm::ConnectionTrait;
+use sea_orm::DatabaseConnection;
+use sea_orm::DeleteResult;
 use sea_orm::EntityTrait;
 use sea_orm::IntoActiveModel;
+use sea_orm::LoaderTrait;
+use sea_orm::ModelTrait;
+use sea_orm::PaginatorTrait;
+use sea_orm::QueryFilter;
+use sea_orm::QueryOrder;
+use sea_orm::QuerySelect;
+use sea_orm::QueryTrait;
+use sea_orm::Select;
+use sea_orm::TransactionTrait;
+use sea_orm::TryIntoModel;
+use std::collections::BTreeMap;
 
 lazy_static! {
     static ref settings: Config = Config::builder()
@@ -27,50 +100,553 @@ lazy_static! {
 
 #[derive(Debug, serde::Deserialize)]
 pub struct GameIDQuery {
-    pub gameid: u32,
+    pub gameid: i32,
 }
 
 #[derive(Debug, serde::Deserialize)]
 pub struct GameNameQuery {
     pub gamename: String,
+    /// 只返回至少有一份在该型号 CPU 上测试的报告的游戏。
+    pub cpu: Option<String>,
+    /// 只返回至少有一份该 ABI 报告的游戏。
+    pub abi: Option<game::Abi>,
+    /// 只返回至少有一份该发行版报告的游戏。
+    pub distributionid: Option<i32>,
+    /// 只返回带有该标签的游戏。
+    pub tag: Option<String>,
+    /// 只返回需要该运行库的游戏。
+    pub dependency: Option<String>,
+    /// 与 `dependency` 同用，只返回版本要求允许该版本的游戏。
+    pub dependency_version: Option<String>,
+    /// 只返回评级完全相同的游戏，如 "AA"。
+    pub grade: Option<String>,
+    /// 为真时将 DLC 折叠到其本体之下，本体未被搜到时也会一并返回；本体已被删除的 DLC 不折叠。
+    #[serde(default)]
+    pub collapse: bool,
+    /// 只返回评级不低于该值的游戏。
+    pub min_grade: Option<String>,
+    pub sort: Option<SortKey>,
+}
+
+/// 浏览游戏列表时的筛选、排序与分页参数。
+#[derive(Debug, serde::Deserialize)]
+pub struct GameListQuery {
+    pub supportlevel: Option<game::SupportLevel>,
+    /// 只返回具备其中全部兼容层的游戏，按位标志表示。
+    pub compat: Option<game::Compatibility>,
+    /// 只返回不具备其中任何兼容层的游戏，按位标志表示。
+    pub exclude: Option<game::Compatibility>,
+    /// 只返回名称以此开头的游戏。
+    pub prefix: Option<String>,
+    /// 只返回至少有一份在该型号 CPU 上测试的报告的游戏。
+    pub cpu: Option<String>,
+    /// 只返回至少有一份该 ABI 报告的游戏。
+    pub abi: Option<game::Abi>,
+    /// 只返回至少有一份该发行版报告的游戏。
+    pub distributionid: Option<i32>,
+    /// 未指定时按 id 排序。
+    pub sort: Option<SortKey>,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default = "default_page_size")]
+    pub limit: u64,
+}
+
+fn default_page_size() -> u64 {
+    20
+}
+
+/// 每页最多返回的游戏数。
+const MAX_PAGE_SIZE: u64 = 100;
+
+/// 列表的排序方式，未指定时按搜索相关度排序。
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    NAME,
+    ID,
+    GRADE,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct HardwareIDQuery {
+    pub hardwareid: i32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct AliasIDQuery {
+    pub aliasid: i32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ExternalIDQuery {
+    pub store: external_id::Store,
+    pub id: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct LayerListQuery {
+    pub kind: Option<layer::LayerKind>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct DependencyListQuery {
+    pub kind: Option<dependency::DependencyKind>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct TagListQuery {
+    pub kind: Option<tag::TagKind>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ReportListQuery {
+    pub gameid: i32,
+    /// 只返回在该型号 CPU 上测试的报告。
+    pub cpu: Option<String>,
+    pub abi: Option<game::Abi>,
+    pub distributionid: Option<i32>,
+    /// 只返回针对该游戏版本的报告。
+    pub buildid: Option<i32>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ReportIDQuery {
+    pub reportid: i32,
+}
+
+/// 新增游戏时的请求体，id 由数据库分配，置信度由报告汇总得出。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct GameRequest {
+    pub name: String,
+    pub supportlevel: game::SupportLevel,
+    #[serde(default)]
+    pub compat: game::Compatibility,
+    pub developer: Option<String>,
+    pub publisher: Option<String>,
+    pub release_year: Option<i16>,
+    pub parent_id: Option<i32>,
+    pub parent_kind: Option<game::ParentKind>,
+}
+
+/// 提交报告时的请求体，测试者与提交时间由服务端填写。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ReportRequest {
+    pub gameid: i32,
+    pub supportlevel: game::SupportLevel,
+    /// 旧版位标志，可与 `layers` 同时提供，两者会合并。
+    #[serde(default)]
+    pub compat: game::Compatibility,
+    /// 测试时使用的兼容层登记表 id。
+    #[serde(default)]
+    pub layers: Vec<i32>,
+    pub hardwareid: Option<i32>,
+    pub distributionid: Option<i32>,
+    /// 测试的游戏版本，须属于同一游戏。
+    pub buildid: Option<i32>,
+    pub abi: Option<game::Abi>,
+    pub kernel: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// 新增硬件时的请求体，id 由数据库分配。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct HardwareRequest {
+    pub cpu: String,
+    pub board: Option<String>,
+    pub ram: Option<i32>,
+    pub gpu: Option<String>,
+}
+
+/// 新增发行版时的请求体，id 由数据库分配。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct DistributionRequest {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// 新增兼容层时的请求体，id 由数据库分配。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LayerRequest {
+    pub name: String,
+    pub kind: layer::LayerKind,
+    pub version: Option<String>,
+}
+
+/// 新增别名时的请求体，id 由数据库分配。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct AliasRequest {
+    pub gameid: i32,
+    pub name: String,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+/// 登记游戏版本时的请求体，id 由数据库分配，运行效果由报告汇总得出。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct BuildRequest {
+    pub gameid: i32,
+    pub version: String,
+    pub edition: Option<String>,
+    pub released_on: Option<chrono::NaiveDate>,
+    pub exe_sha256: Option<String>,
+}
+
+/// 新增运行库时的请求体，id 由数据库分配。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct DependencyRequest {
+    pub name: String,
+    pub kind: dependency::DependencyKind,
+    pub winetricks: Option<String>,
+}
+
+/// 新增标签时的请求体，id 由数据库分配。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct TagRequest {
+    pub name: String,
+    pub kind: tag::TagKind,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct GuideIDQuery {
+    pub guideid: i32,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ExportQuery {
+    pub gameid: i32,
+    pub format: export::ExportFormat,
+    /// 使用的指南，缺省时取该游戏最新的指南。
+    pub guideid: Option<i32>,
+}
+
+/// 新增指南时的请求体，作者与提交时间由服务端填写。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct GuideRequest {
+    pub gameid: i32,
+    /// 验证过该配置的报告，须属于同一游戏。
+    pub reportid: Option<i32>,
+    pub title: String,
+    pub wine_prefix: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub winetricks: Vec<String>,
+    pub executable: Option<String>,
+    /// 启动命令及其参数，每一项为一个参数。
+    pub command: Option<Vec<String>>,
+    /// 按顺序排列的步骤，均为 Markdown。
+    #[serde(default)]
+    pub steps: Vec<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct IssueListQuery {
+    pub gameid: i32,
+    /// 为真时同时返回已解决的问题。
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// 新增已知问题时的请求体，提交者与提交时间由服务端填写。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct IssueRequest {
+    pub gameid: i32,
+    pub title: String,
+    pub severity: issue::Severity,
+    /// 受影响的兼容层登记表 id，登记表中每一行对应一个版本。
+    #[serde(default)]
+    pub layers: Vec<i32>,
+    /// Markdown，存储前去掉原始 HTML。
+    pub workaround: Option<String>,
+    pub bug_url: Option<String>,
+    pub fixed_in: Option<String>,
+}
+
+/// 标记问题已在某版本修复，`fixed_in` 为空时重新打开。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct IssueFixRequest {
+    pub issueid: i32,
+    pub fixed_in: Option<String>,
+}
+
+/// 设置测试者信誉时的请求体。
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct TesterRequest {
+    pub name: String,
+    pub reputation: f64,
+}
+
+/// 取出已登录用户的用户名，未登录时返回应直接交给客户端的响应。
+fn require_login(user: Option<Identity>) -> Result<String, HttpResponse> {
+    match user.map(|user| user.id()) {
+        Some(Ok(username)) => Ok(username),
+        _ => {
+            let response = BasicResponse {
+                code: ResponseCode::LoginRequired.into(),
+                message: "Login required.",
+            };
+            Err(HttpResponse::Unauthorized().json(response))
+        }
+    }
+}
+
+/// 用户是否位于设置项 `ADMINS` 中。
+fn is_admin(username: &str) -> bool {
+    let admins = settings.get_array("ADMINS").unwrap_or_default();
+    admins
+        .into_iter()
+        .any(|admin| admin.into_string().is_ok_and(|admin| admin == username))
+}
+
+/// 要求当前用户位于设置项 `ADMINS` 中。
+fn require_admin(user: Option<Identity>) -> Result<String, HttpResponse> {
+    let username = require_login(user)?;
+    if is_admin(&username) {
+        Ok(username)
+    } else {
+        let response = BasicResponse {
+            code: ResponseCode::PermissionDenied.into(),
+            message: "Permission denied.",
+        };
+        Err(HttpResponse::Forbidden().json(response))
+    }
+}
+
+/// 重新将游戏名与全部别名写入 Sonic，已软删除的游戏只会被移出索引。
+async fn reindex_game(db: &sea_orm::DatabaseConnection, gameid: i32) -> Result<(), String> {
+    let game = Entity::find_by_id(gameid)
+        .one(db)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(game) = game else {
+        return Err("Game not found.".to_string());
+    };
+    if game.deleted_at.is_some() {
+        return sonic::sonic_delete_game(gameid);
+    }
+    let aliases = game
+        .find_related(alias::Entity)
+        .all(db)
+        .await
+        .map_err(|e| e.to_string())?;
+    sonic::sonic_write_game(game, aliases)
+}
+
+/// 组装游戏详情，附带别名、兼容层等关联数据。
+async fn game_info(
+    db: &sea_orm::DatabaseConnection,
+    game: Option<game::Model>,
+) -> Result<InfoResponse<'static>, sea_orm::DbErr> {
+    let mut response = InfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        game: None,
+        grade: None,
+        parent: None,
+        children: Vec::new(),
+        stacks: Vec::new(),
+        aliases: Vec::new(),
+        layers: Vec::new(),
+        external_ids: Vec::new(),
+        tags: Vec::new(),
+        issues: Vec::new(),
+        dependencies: Vec::new(),
+        builds: Vec::new(),
+    };
+    if let Some(game) = game {
+        response.aliases = game.find_related(alias::Entity).all(db).await?;
+        let links = game_layer::find_links(db, [game.id]).await?;
+        response.external_ids = game.find_related(external_id::Entity).all(db).await?;
+        response.tags = game.find_related(tag::Entity).all(db).await?;
+        let issues = issue::Entity::find_open()
+            .filter(issue::Column::Gameid.eq(game.id))
+            .order_by_asc(issue::Column::Severity)
+            .all(db)
+            .await?;
+        response.issues = issue_details(db, issues).await?;
+        let dependencies = game_dependency::Entity::find()
+            .filter(game_dependency::Column::Gameid.eq(game.id))
+            .find_also_related(dependency::Entity)
+            .all(db)
+            .await?;
+        response.dependencies = dependencies
+            .into_iter()
+            .filter_map(|(link, dependency)| {
+                Some(GameDependencyDetail {
+                    dependency: dependency?,
+                    version: link.version,
+                })
+            })
+            .collect();
+        response.builds = find_builds(db, game.id).await?;
+        response.parent = game
+            .find_linked(game::ParentLink)
+            .filter(game::Column::DeletedAt.is_null())
+            .one(db)
+            .await?;
+        response.children = game
+            .find_linked(game::ChildrenLink)
+            .filter(game::Column::DeletedAt.is_null())
+            .order_by_asc(game::Column::Id)
+            .all(db)
+            .await?;
+        let stacks = game.find_related(game_stack::Entity).all(db).await?;
+        response.grade = Some(game.grading_with(&stacks, &links));
+        response.stacks = stacks
+            .into_iter()
+            .map(|stack| StackDetail {
+                grade: stack.grading(&links),
+                stack,
+            })
+            .collect();
+        response.layers = links
+            .into_iter()
+            .map(|(link, layer)| GameLayerDetail {
+                layer,
+                role: link.role,
+            })
+            .collect();
+        response.game = Some(game);
+    }
+    Ok(response)
+}
+
+/// 查询游戏的全部版本，按发布日期从新到旧排列，未填日期的排在最后。
+async fn find_builds(
+    db: &sea_orm::DatabaseConnection,
+    gameid: i32,
+) -> Result<Vec<BuildDetail>, sea_orm::DbErr> {
+    let mut builds = build::Entity::find()
+        .filter(build::Column::Gameid.eq(gameid))
+        .order_by_desc(build::Column::Id)
+        .all(db)
+        .await?;
+    builds.sort_by_key(|build| std::cmp::Reverse(build.released_on));
+    Ok(builds
+        .into_iter()
+        .map(|build| BuildDetail {
+            known_good: build.known_good(),
+            build,
+        })
+        .collect())
+}
+
+/// 为问题附上受影响的兼容层版本。
+async fn issue_details(
+    db: &sea_orm::DatabaseConnection,
+    issues: Vec<issue::Model>,
+) -> Result<Vec<IssueDetail>, sea_orm::DbErr> {
+    let layers = issues
+        .load_many_to_many(layer::Entity, issue_layer::Entity, db)
+        .await?;
+    Ok(issues
+        .into_iter()
+        .zip(layers)
+        .map(|(issue, layers)| IssueDetail { issue, layers })
+        .collect())
+}
+
+/// 为游戏附上标签与评级，`dlcs` 中属于某个游戏的 DLC 会折叠到其下。
+async fn game_summaries(
+    db: &sea_orm::DatabaseConnection,
+    games: Vec<game::Model>,
+    dlcs: &[game::Model],
+) -> Result<Vec<GameSummary>, sea_orm::DbErr> {
+    let tags = games
+        .load_many_to_many(tag::Entity, game_tag::Entity, db)
+        .await?;
+    let stacks = games.load_many(game_stack::Entity, db).await?;
+    let links = game_layer::find_links(db, games.iter().map(|game| game.id)).await?;
+    Ok(games
+        .into_iter()
+        .zip(tags)
+        .zip(stacks)
+        .map(|((game, tags), stacks)| {
+            let links: Vec<game_layer::LayerLink> = links
+                .iter()
+                .filter(|(link, _)| link.gameid == game.id)
+                .cloned()
+                .collect();
+            GameSummary {
+                grade: game.grading_with(&stacks, &links),
+                dlcs: dlcs
+                    .iter()
+                    .filter(|dlc| dlc.parent_id == Some(game.id))
+                    .cloned()
+                    .collect(),
+                game,
+                tags,
+            }
+        })
+        .collect())
 }
 
 #[get("/info")]
-pub async fn info(query: Query<GameIDQuery>) -> HttpResponse {
-    // TODO: Reuse db connection
-    let db = Database::connect(settings.get_string("DATABASE_URL").unwrap()).await;
-    if db.is_err() {
-        let message = format!("Failed to connect to database: {}", db.err().unwrap());
+pub async fn info(db: Data<DatabaseConnection>, query: Query<GameIDQuery>) -> HttpResponse {
+    let gameid = query.gameid;
+    let game = Entity::find_visible()
+        .filter(game::Column::Id.eq(gameid))
+        .one(db.get_ref())
+        .await;
+    dbg!(&game);
+    if game.is_err() {
+        let message = format!("Failed to query game: {}", game.err().unwrap());
         let response = BasicResponse {
             code: ResponseCode::DatabaseConnectionError.into(),
             message: message.as_str(),
         };
         return HttpResponse::BadRequest().json(response);
     }
-    let db = db.unwrap();
-    let gameid = query.gameid;
-    let game = Entity::find_by_id(gameid).one(&db).await;
-    dbg!(&game);
-    let response = InfoResponse {
-        code: ResponseCode::Success.into(),
-        message: "OK",
-        game: game.unwrap(),
-    };
-    HttpResponse::Ok().json(response)
+    let response = game_info(db.get_ref(), game.unwrap()).await;
+    if response.is_err() {
+        let message = format!("Failed to query game info: {}", response.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    HttpResponse::Ok().json(response.unwrap())
 }
 
-#[get("/search")]
-pub async fn search(query: Query<GameNameQuery>) -> HttpResponse {
-    let db = Database::connect(settings.get_string("DATABASE_URL").unwrap()).await;
-    if db.is_err() {
-        let message = format!("Failed to connect to database: {}", db.err().unwrap());
+#[get("/info/by-external")]
+pub async fn info_by_external(
+    db: Data<DatabaseConnection>,
+    query: Query<ExternalIDQuery>,
+) -> HttpResponse {
+    let query = query.into_inner();
+    let game = external_id::Entity::find_by_id((query.store, query.id))
+        .find_also_related(game::Entity)
+        .one(db.get_ref())
+        .await;
+    if game.is_err() {
+        let message = format!("Failed to query game: {}", game.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let game = game
+        .unwrap()
+        .and_then(|(_, game)| game)
+        .filter(|game| game.deleted_at.is_none());
+    let response = game_info(db.get_ref(), game).await;
+    if response.is_err() {
+        let message = format!("Failed to query game info: {}", response.err().unwrap());
         let response = BasicResponse {
             code: ResponseCode::DatabaseConnectionError.into(),
             message: message.as_str(),
         };
         return HttpResponse::BadRequest().json(response);
     }
-    let db = db.unwrap();
+    HttpResponse::Ok().json(response.unwrap())
+}
+
+#[get("/search")]
+pub async fn search(db: Data<DatabaseConnection>, query: Query<GameNameQuery>) -> HttpResponse {
     let gamename = query.gamename.to_string();
     let games = sonic::sonic_read_game(gamename);
     if games.is_err() {
@@ -82,15 +658,139 @@ pub async fn search(query: Query<GameNameQuery>) -> HttpResponse {
         return HttpResponse::BadRequest().json(response);
     }
     dbg!(&games);
-    let games = join_all(
-        games
+    let mut ids = games.unwrap();
+    let reports = report_filter(
+        query.cpu.as_deref(),
+        query.abi.as_ref(),
+        query.distributionid,
+    );
+    if let Some(reports) = reports {
+        let result = retain_reported(db.get_ref(), &mut ids, reports).await;
+        if result.is_err() {
+            let message = format!("Failed to query reports: {}", result.err().unwrap());
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    if let Some(tag) = &query.tag {
+        let tagged = game_tag::Entity::find()
+            .inner_join(tag::Entity)
+            .filter(tag::Column::Name.eq(tag.as_str()))
+            .filter(game_tag::Column::Gameid.is_in(ids.clone()))
+            .all(db.get_ref())
+            .await;
+        if tagged.is_err() {
+            let message = format!("Failed to query tags: {}", tagged.err().unwrap());
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+        let matched: Vec<i32> = tagged.unwrap().iter().map(|t| t.gameid).collect();
+        ids.retain(|id| matched.contains(id));
+    }
+    if let Some(name) = &query.dependency {
+        let needed = game_dependency::Entity::find()
+            .inner_join(dependency::Entity)
+            .filter(dependency::Column::Name.eq(name.as_str()))
+            .filter(game_dependency::Column::Gameid.is_in(ids.clone()))
+            .all(db.get_ref())
+            .await;
+        if needed.is_err() {
+            let message = format!("Failed to query dependencies: {}", needed.err().unwrap());
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+        let matched: Vec<i32> = needed
             .unwrap()
-            .iter()
-            .map(|id| async { game::Entity::find_by_id(*id).one(&db).await.unwrap() }),
-    )
+            .into_iter()
+            .filter(|link| match (&link.version, &query.dependency_version) {
+                (Some(constraint), Some(version)) => {
+                    game_dependency::satisfies(constraint, version)
+                }
+                _ => true,
+            })
+            .map(|link| link.gameid)
+            .collect();
+        ids.retain(|id| matched.contains(id));
+    }
+    let games = join_all(ids.iter().map(|id| async {
+        game::Entity::find_visible()
+            .filter(game::Column::Id.eq(*id))
+            .one(db.get_ref())
+            .await
+            .unwrap()
+    }))
     .await;
-    let games: Vec<game::Model> = games.into_iter().map(|game| game.unwrap()).collect();
+    // Sonic 中可能残留已删除的游戏
+    let mut games: Vec<game::Model> = games.into_iter().flatten().collect();
+    let mut dlcs: Vec<game::Model> = Vec::new();
+    if query.collapse {
+        let (collapsed, rest): (Vec<game::Model>, Vec<game::Model>) =
+            games.into_iter().partition(|game| {
+                game.parent_id.is_some() && game.parent_kind == Some(game::ParentKind::DLC)
+            });
+        games = rest;
+        dlcs = collapsed;
+        // 只搜到 DLC 时补上其本体
+        let missing: Vec<i32> = dlcs
+            .iter()
+            .filter_map(|dlc| dlc.parent_id)
+            .filter(|id| !games.iter().any(|game| game.id == *id))
+            .collect();
+        if !missing.is_empty() {
+            let parents = game::Entity::find_visible()
+                .filter(game::Column::Id.is_in(missing))
+                .all(db.get_ref())
+                .await;
+            if parents.is_err() {
+                let message = format!("Failed to query games: {}", parents.err().unwrap());
+                let response = BasicResponse {
+                    code: ResponseCode::DatabaseConnectionError.into(),
+                    message: message.as_str(),
+                };
+                return HttpResponse::BadRequest().json(response);
+            }
+            games.extend(parents.unwrap());
+        }
+        // 本体已被删除的 DLC 无处折叠，仍作为独立的结果返回
+        let (folded, orphans): (Vec<game::Model>, Vec<game::Model>) = dlcs
+            .into_iter()
+            .partition(|dlc| games.iter().any(|game| Some(game.id) == dlc.parent_id));
+        games.extend(orphans);
+        dlcs = folded;
+    }
     dbg!(&games);
+    let games = game_summaries(db.get_ref(), games, &dlcs).await;
+    if games.is_err() {
+        let message = format!("Failed to query game details: {}", games.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let mut games = games.unwrap();
+    if let Some(grade) = &query.grade {
+        games.retain(|game| &game.grade == grade);
+    }
+    if let Some(min_grade) = &query.min_grade {
+        let min_grade = game::grade_order(min_grade);
+        games.retain(|game| game::grade_order(&game.grade) <= min_grade);
+    }
+    match query.sort {
+        Some(SortKey::NAME) => games.sort_by(|a, b| a.game.name.cmp(&b.game.name)),
+        Some(SortKey::ID) => games.sort_by_key(|game| game.game.id),
+        Some(SortKey::GRADE) => games.sort_by_key(|game| game::grade_order(&game.grade)),
+        None => {}
+    }
     let response = SearchResponse {
         code: ResponseCode::Success.into(),
         message: "OK",
@@ -99,41 +799,446 @@ pub async fn search(query: Query<GameNameQuery>) -> HttpResponse {
     HttpResponse::Ok().json(response)
 }
 
+/// 以反斜杠转义 LIKE 模式中的通配符。
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 按测试所用 CPU 型号、ABI 与发行版筛选报告；三者均未指定时返回 `None`。
+/// `/report/list` 直接使用，`/search` 与 `/games` 用它只保留有符合条件报告的游戏。
+fn report_filter(
+    cpu: Option<&str>,
+    abi: Option<&game::Abi>,
+    distributionid: Option<i32>,
+) -> Option<Select<report::Entity>> {
+    if cpu.is_none() && abi.is_none() && distributionid.is_none() {
+        return None;
+    }
+    let mut select = report::Entity::find();
+    if let Some(cpu) = cpu {
+        select = select
+            .inner_join(hardware::Entity)
+            .filter(hardware::Column::Cpu.eq(cpu));
+    }
+    if let Some(abi) = abi {
+        select = select.filter(report::Column::Abi.eq(abi.clone()));
+    }
+    if let Some(distributionid) = distributionid {
+        select = select.filter(report::Column::Distributionid.eq(distributionid));
+    }
+    Some(select)
+}
+
+/// 只保留 `ids` 中存在符合 `reports` 条件报告的游戏，顺序不变。
+async fn retain_reported<C: ConnectionTrait>(
+    db: &C,
+    ids: &mut Vec<i32>,
+    reports: Select<report::Entity>,
+) -> Result<(), sea_orm::DbErr> {
+    let matched: Vec<i32> = reports
+        .filter(report::Column::Gameid.is_in(ids.clone()))
+        .select_only()
+        .column(report::Column::Gameid)
+        .into_tuple()
+        .all(db)
+        .await?;
+    ids.retain(|id| matched.contains(id));
+    Ok(())
+}
+
+/// 分页浏览全部游戏，可按运行效果、兼容层与名称前缀筛选。
+/// 按评级排序时使用 `games.grade_rank` 中缓存的排序键。
+#[get("/games")]
+pub async fn game_list(db: Data<DatabaseConnection>, query: Query<GameListQuery>) -> HttpResponse {
+    let limit = query.limit.clamp(1, MAX_PAGE_SIZE);
+    let mut select = Entity::find_visible();
+    if let Some(supportlevel) = &query.supportlevel {
+        select = select.filter(game::Column::Supportlevel.eq(supportlevel.clone()));
+    }
+    if let Some(compat) = query.compat {
+        select = select.filter(compat.contained_by_column());
+    }
+    if let Some(exclude) = query.exclude {
+        select = select.filter(exclude.excluded_by_column());
+    }
+    if let Some(prefix) = &query.prefix {
+        // 前缀中的 % 与 _ 应按字面匹配
+        let pattern = format!("{}%", escape_like(prefix));
+        select = select.filter(game::Column::Name.like(LikeExpr::new(pattern).escape('\\')));
+    }
+    let reports = report_filter(
+        query.cpu.as_deref(),
+        query.abi.as_ref(),
+        query.distributionid,
+    );
+    if let Some(reports) = reports {
+        let gameids = reports
+            .select_only()
+            .column(report::Column::Gameid)
+            .into_query();
+        select = select.filter(game::Column::Id.in_subquery(gameids));
+    }
+    let total = select.clone().count(db.get_ref()).await;
+    if total.is_err() {
+        let message = format!("Failed to query games: {}", total.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let select = match query.sort {
+        Some(SortKey::NAME) => select.order_by_asc(game::Column::Name),
+        // 尚未计算排序键的游戏排在最后
+        Some(SortKey::GRADE) => select
+            .order_by_asc(Expr::col(game::Column::GradeRank).is_null())
+            .order_by_asc(game::Column::GradeRank),
+        Some(SortKey::ID) | None => select,
+    };
+    let games = select
+        .order_by_asc(game::Column::Id)
+        .offset(query.offset)
+        .limit(limit)
+        .all(db.get_ref())
+        .await;
+    if games.is_err() {
+        let message = format!("Failed to query games: {}", games.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let games = game_summaries(db.get_ref(), games.unwrap(), &[]).await;
+    if games.is_err() {
+        let message = format!("Failed to query game details: {}", games.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let games = games.unwrap();
+    let response = GameListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        total: total.unwrap(),
+        offset: query.offset,
+        limit,
+        games,
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 说明评级中各字母与重复次数的含义，供前端展示。
+#[get("/grades")]
+pub async fn grades() -> HttpResponse {
+    let rules = grading::rules().clone();
+    let response = GradeLegendResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        letters: grading::LEVEL_DESCRIPTIONS
+            .into_iter()
+            .map(|(supportlevel, description)| GradeLetter {
+                letter: rules.letter(&supportlevel).to_string(),
+                supportlevel,
+                description,
+            })
+            .collect(),
+        repeats: rules
+            .repeat_descriptions()
+            .into_iter()
+            .map(|(repeat, description)| GradeRepeat {
+                repeat,
+                description,
+            })
+            .collect(),
+        rules,
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 重新读取设置中的评级规则，之后的评级均按新规则计算，并更新缓存的评级排序键，仅管理员可用。
+#[post("/grades/reload")]
+pub async fn grades_reload(db: Data<DatabaseConnection>, user: Option<Identity>) -> HttpResponse {
+    if let Err(response) = require_admin(user) {
+        return response;
+    }
+    let result = grading::reload();
+    if let Err(e) = result {
+        let message = format!("Failed to reload grading rules: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::SystemInternalError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    // 数据库中的评级排序键按旧规则计算，需要全部更新
+    let result = aggregation::regrade_all(db.get_ref(), false).await;
+    if let Err(e) = result {
+        let message = format!("Failed to update grades: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 重新读取设置中的汇总规则，并按新规则重新汇总全部游戏的报告，仅管理员可用。
+#[post("/aggregation/reload")]
+pub async fn aggregation_reload(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+) -> HttpResponse {
+    if let Err(response) = require_admin(user) {
+        return response;
+    }
+    if let Err(e) = aggregation::reload() {
+        let message = format!("Failed to reload aggregation rules: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::SystemInternalError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if let Err(e) = aggregation::recompute_all(db.get_ref()).await {
+        let message = format!("Failed to aggregate reports: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
 #[post("/add")]
-pub async fn add(data: Json<game::Model>) -> HttpResponse {
+pub async fn add(db: Data<DatabaseConnection>, data: Json<GameRequest>) -> HttpResponse {
     dbg!(&data);
-    let data_active = data.clone().into_active_model();
+    let data = data.into_inner();
+    let game = game::Model {
+        name: data.name,
+        // 插入前尚未分配 id，0 不会与任何已有游戏冲突
+        id: 0,
+        supportlevel: data.supportlevel,
+        compat: data.compat,
+        confidence: None,
+        developer: data.developer,
+        publisher: data.publisher,
+        release_year: data.release_year,
+        parent_id: data.parent_id,
+        parent_kind: data.parent_kind,
+        deleted_at: None,
+        grade_rank: None,
+        best_supportlevel: None,
+        best_confidence: None,
+    };
+    match game.check_parent(db.get_ref()).await {
+        Ok(None) => {}
+        Ok(Some(reason)) => {
+            let response = BasicResponse {
+                code: ResponseCode::InvalidRequest.into(),
+                message: reason,
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query parent game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    // 新游戏尚无报告与兼容层关联，评级只取决于自身的字段
+    let grade_rank = game::grade_rank(&game.grading());
+    let mut data_active = game.into_active_model();
+    data_active.id = ActiveValue::NotSet;
+    data_active.grade_rank = ActiveValue::Set(Some(grade_rank));
     dbg!(&data_active);
-    let db = Database::connect(settings.get_string("DATABASE_URL").unwrap()).await;
-    if db.is_err() {
-        let message = format!("Failed to connect to database: {}", db.err().unwrap());
+    let result = data_active.insert(db.get_ref()).await;
+    dbg!(&result);
+    let game = match result {
+        Ok(game) => game,
+        Err(e) => {
+            let message = format!("Failed to insert game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    let result = sonic::sonic_write_game(game.clone(), Vec::new());
+    dbg!(&result);
+    if result.is_err() {
+        let message = format!("Failed to insert game: {}", result.err().unwrap());
         let response = BasicResponse {
             code: ResponseCode::DatabaseConnectionError.into(),
             message: message.as_str(),
         };
         return HttpResponse::BadRequest().json(response);
     }
-    let db = db.unwrap();
-    let result = data_active.insert(&db).await;
-    dbg!(&result);
+
+    let response = AddResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        game: Some(game),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 按 PATCH 语义更新游戏，请求体中只需包含要修改的字段。名称变化时会同步更新 Sonic 索引。
+/// 只读字段与原值不同、或游戏已有报告时修改运行效果，均返回 `InvalidRequest`。
+#[post("/update")]
+pub async fn update(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<GameIDQuery>,
+    data: Json<sea_orm::JsonValue>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let game = Entity::find_visible()
+        .filter(game::Column::Id.eq(query.gameid))
+        .one(db.get_ref())
+        .await;
+    let game = match game {
+        Ok(Some(game)) => game,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    let old_name = game.name.clone();
+    let old_level = game.supportlevel.clone();
+    let game_active = game.patch(&data);
+    if game_active.is_err() {
+        let message = format!("Invalid update: {}", game_active.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let game_active = game_active.unwrap();
+    // 有报告后运行效果由汇总得出，只有尚无报告的游戏可以手动填写
+    if game_active.supportlevel.is_set() && *game_active.supportlevel.as_ref() != old_level {
+        let reported = report::Entity::find()
+            .filter(report::Column::Gameid.eq(query.gameid))
+            .count(db.get_ref())
+            .await;
+        match reported {
+            Ok(0) => {}
+            Ok(_) => {
+                let response = BasicResponse {
+                    code: ResponseCode::InvalidRequest.into(),
+                    message: "Support level is aggregated from reports and cannot be updated.",
+                };
+                return HttpResponse::BadRequest().json(response);
+            }
+            Err(e) => {
+                let message = format!("Failed to query reports: {}", e);
+                let response = BasicResponse {
+                    code: ResponseCode::DatabaseConnectionError.into(),
+                    message: message.as_str(),
+                };
+                return HttpResponse::BadRequest().json(response);
+            }
+        }
+    }
+    if game_active.parent_id.is_set() || game_active.parent_kind.is_set() {
+        let patched = game_active.clone().try_into_model();
+        let checked = match patched {
+            Ok(patched) => patched.check_parent(db.get_ref()).await,
+            Err(e) => Err(e),
+        };
+        match checked {
+            Ok(None) => {}
+            Ok(Some(reason)) => {
+                let response = BasicResponse {
+                    code: ResponseCode::InvalidRequest.into(),
+                    message: reason,
+                };
+                return HttpResponse::BadRequest().json(response);
+            }
+            Err(e) => {
+                let message = format!("Failed to query parent game: {}", e);
+                let response = BasicResponse {
+                    code: ResponseCode::DatabaseConnectionError.into(),
+                    message: message.as_str(),
+                };
+                return HttpResponse::BadRequest().json(response);
+            }
+        }
+    }
+    if !game_active.is_changed() {
+        let response = BasicResponse {
+            code: ResponseCode::Success.into(),
+            message: "OK",
+        };
+        return HttpResponse::Ok().json(response);
+    }
+    let result = game_active.update(db.get_ref()).await;
     if result.is_err() {
-        let message = format!("Failed to insert game: {}", result.err().unwrap());
+        let message = format!("Failed to update game: {}", result.err().unwrap());
         let response = BasicResponse {
             code: ResponseCode::DatabaseConnectionError.into(),
             message: message.as_str(),
         };
         return HttpResponse::BadRequest().json(response);
     }
-    let result = sonic::sonic_write_game(data.clone());
-    dbg!(&result);
+    let game = result.unwrap();
+    let result = aggregation::regrade(db.get_ref(), game.id).await;
     if result.is_err() {
-        let message = format!("Failed to insert game: {}", result.err().unwrap());
+        let message = format!("Failed to update grade: {}", result.err().unwrap());
         let response = BasicResponse {
             code: ResponseCode::DatabaseConnectionError.into(),
             message: message.as_str(),
         };
         return HttpResponse::BadRequest().json(response);
     }
+    if game.name != old_name {
+        let result = reindex_game(db.get_ref(), query.gameid).await;
+        if result.is_err() {
+            let message = format!("Failed to update index: {}", result.err().unwrap());
+            let response = BasicResponse {
+                code: ResponseCode::SonicDBConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
 
     let response = BasicResponse {
         code: ResponseCode::Success.into(),
@@ -142,15 +1247,2462 @@ pub async fn add(data: Json<game::Model>) -> HttpResponse {
     HttpResponse::Ok().json(response)
 }
 
-#[post("/delete")] // 软删除?
-pub async fn delete(query: Query<GameIDQuery>) -> HttpResponse {
-    let gameid = query.gameid;
-    let game = Entity::find_by_id(gameid);
-    dbg!(&game);
-    // HttpResponse::Ok().body("dummy")
+/// 软删除游戏：保留数据库中的记录，但从 `/info`、`/search` 与 Sonic 索引中隐藏，仅管理员可用。
+#[post("/delete")]
+pub async fn delete(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<GameIDQuery>,
+) -> HttpResponse {
+    if let Err(response) = require_admin(user) {
+        return response;
+    }
+    set_deleted(db.get_ref(), query.gameid, Some(chrono::Utc::now())).await
+}
+
+/// 恢复被软删除的游戏，并重新写入 Sonic 索引，仅管理员可用。
+#[post("/restore")]
+pub async fn restore(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<GameIDQuery>,
+) -> HttpResponse {
+    if let Err(response) = require_admin(user) {
+        return response;
+    }
+    set_deleted(db.get_ref(), query.gameid, None).await
+}
+
+async fn set_deleted(
+    db: &DatabaseConnection,
+    gameid: i32,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> HttpResponse {
+    let game = Entity::find_by_id(gameid).one(db).await;
+    let game = match game {
+        Ok(Some(game)) => game,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    let mut game = game.into_active_model();
+    game.deleted_at = ActiveValue::Set(deleted_at);
+    let result = game.update(db).await;
+    if result.is_err() {
+        let message = format!("Failed to update game: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let result = reindex_game(db, gameid).await;
+    if result.is_err() {
+        let message = format!("Failed to update index: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::SonicDBConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 彻底删除游戏及其全部关联数据，仅管理员可用。
+#[post("/purge")]
+pub async fn purge(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<GameIDQuery>,
+) -> HttpResponse {
+    if let Err(response) = require_admin(user) {
+        return response;
+    }
+    // 报告、别名、关联表等通过外键级联删除。SQLite 上 games.parent_id 没有外键，
+    // 下级游戏的上级需手动清空
+    let gameid = query.gameid;
+    let result = db
+        .transaction::<_, DeleteResult, sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                Entity::update_many()
+                    .col_expr(game::Column::ParentId, Expr::value(Option::<i32>::None))
+                    .col_expr(game::Column::ParentKind, Expr::value(Option::<i32>::None))
+                    .filter(game::Column::ParentId.eq(gameid))
+                    .exec(txn)
+                    .await?;
+                Entity::delete_by_id(gameid).exec(txn).await
+            })
+        })
+        .await;
+    match result {
+        Ok(result) if result.rows_affected == 0 => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Ok(_) => {}
+        Err(e) => {
+            let message = format!("Failed to purge game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    let result = sonic::sonic_delete_game(query.gameid);
+    if result.is_err() {
+        let message = format!("Failed to update index: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::SonicDBConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/report/add")]
+pub async fn report_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<ReportRequest>,
+) -> HttpResponse {
+    let tester = match require_login(user) {
+        Ok(tester) => tester,
+        Err(response) => return response,
+    };
+    let game = Entity::find_visible()
+        .filter(game::Column::Id.eq(data.gameid))
+        .one(db.get_ref())
+        .await;
+    if let Err(e) = game {
+        let message = format!("Failed to query game: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if game.unwrap().is_none() {
+        let response = BasicResponse {
+            code: ResponseCode::GameNotFound.into(),
+            message: "Game not found.",
+        };
+        return HttpResponse::NotFound().json(response);
+    }
+    if let Some(hardwareid) = data.hardwareid {
+        let hardware = hardware::Entity::find_by_id(hardwareid)
+            .one(db.get_ref())
+            .await;
+        if let Err(e) = hardware {
+            let message = format!("Failed to query hardware: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+        if hardware.unwrap().is_none() {
+            let response = BasicResponse {
+                code: ResponseCode::HardwareNotFound.into(),
+                message: "Hardware not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+    }
+    if let Some(distributionid) = data.distributionid {
+        let distribution = distribution::Entity::find_by_id(distributionid)
+            .one(db.get_ref())
+            .await;
+        if let Err(e) = distribution {
+            let message = format!("Failed to query distribution: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+        if distribution.unwrap().is_none() {
+            let response = BasicResponse {
+                code: ResponseCode::DistributionNotFound.into(),
+                message: "Distribution not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+    }
+    if let Some(buildid) = data.buildid {
+        let build = build::Entity::find_by_id(buildid).one(db.get_ref()).await;
+        if let Err(e) = build {
+            let message = format!("Failed to query build: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+        if build
+            .unwrap()
+            .is_none_or(|build| build.gameid != data.gameid)
+        {
+            let response = BasicResponse {
+                code: ResponseCode::BuildNotFound.into(),
+                message: "Build not found for this game.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+    }
+    let layers = layer::Entity::find()
+        .filter(layer::Column::Id.is_in(data.layers.clone()))
+        .all(db.get_ref())
+        .await;
+    if let Err(e) = layers {
+        let message = format!("Failed to query layers: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let layers = layers.unwrap();
+    if data
+        .layers
+        .iter()
+        .any(|id| !layers.iter().any(|l| l.id == *id))
+    {
+        let response = BasicResponse {
+            code: ResponseCode::LayerNotFound.into(),
+            message: "Layer not found.",
+        };
+        return HttpResponse::NotFound().json(response);
+    }
+    let data = data.into_inner();
+    // 保持旧版位标志与关联表一致
+    let compat = game::Compatibility(data.compat.0 | game::Compatibility::from_layers(&layers).0);
+    let report_active = report::ActiveModel {
+        gameid: ActiveValue::Set(data.gameid),
+        tester: ActiveValue::Set(tester),
+        supportlevel: ActiveValue::Set(data.supportlevel),
+        compat: ActiveValue::Set(compat),
+        hardwareid: ActiveValue::Set(data.hardwareid),
+        distributionid: ActiveValue::Set(data.distributionid),
+        buildid: ActiveValue::Set(data.buildid),
+        abi: ActiveValue::Set(data.abi),
+        kernel: ActiveValue::Set(data.kernel),
+        comment: ActiveValue::Set(data.comment),
+        created_at: ActiveValue::Set(chrono::Utc::now()),
+        ..Default::default()
+    };
+    let result = db
+        .transaction::<_, (), sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                let report = report_active.insert(txn).await?;
+                if !layers.is_empty() {
+                    report_layer::Entity::insert_many(layers.iter().map(|layer| {
+                        report_layer::ActiveModel {
+                            reportid: ActiveValue::Set(report.id),
+                            layerid: ActiveValue::Set(layer.id),
+                        }
+                    }))
+                    .exec(txn)
+                    .await?;
+                }
+                aggregation::recompute(txn, report.gameid).await
+            })
+        })
+        .await;
+    if result.is_err() {
+        let message = format!("Failed to insert report: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/report/list")]
+pub async fn report_list(
+    db: Data<DatabaseConnection>,
+    query: Query<ReportListQuery>,
+) -> HttpResponse {
+    let select = report_filter(
+        query.cpu.as_deref(),
+        query.abi.as_ref(),
+        query.distributionid,
+    );
+    let mut select = select
+        .unwrap_or_else(report::Entity::find)
+        .filter(report::Column::Gameid.eq(query.gameid));
+    if let Some(buildid) = query.buildid {
+        select = select.filter(report::Column::Buildid.eq(buildid));
+    }
+    let reports = select
+        .order_by_desc(report::Column::CreatedAt)
+        .all(db.get_ref())
+        .await;
+    if reports.is_err() {
+        let message = format!("Failed to query reports: {}", reports.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let reports = reports.unwrap();
+    let layers = reports
+        .load_many_to_many(layer::Entity, report_layer::Entity, db.get_ref())
+        .await;
+    if layers.is_err() {
+        let message = format!("Failed to query layers: {}", layers.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let reports = reports
+        .into_iter()
+        .zip(layers.unwrap())
+        .map(|(report, layers)| ReportDetail {
+            grade: report.grading(),
+            report,
+            layers,
+        })
+        .collect();
+    let response = ReportListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        reports,
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 删除报告并重新汇总游戏的运行效果，仅报告的提交者或管理员可用。
+#[post("/report/delete")]
+pub async fn report_delete(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<ReportIDQuery>,
+) -> HttpResponse {
+    let username = match require_login(user) {
+        Ok(username) => username,
+        Err(response) => return response,
+    };
+    let report = report::Entity::find_by_id(query.reportid)
+        .one(db.get_ref())
+        .await;
+    let report = match report {
+        Ok(Some(report)) => report,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::ReportNotFound.into(),
+                message: "Report not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query report: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    if report.tester != username && !is_admin(&username) {
+        let response = BasicResponse {
+            code: ResponseCode::PermissionDenied.into(),
+            message: "Permission denied.",
+        };
+        return HttpResponse::Forbidden().json(response);
+    }
+    let result = db
+        .transaction::<_, (), sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                let gameid = report.gameid;
+                // 兼容层关联通过外键级联删除
+                report.delete(txn).await?;
+                aggregation::recompute(txn, gameid).await
+            })
+        })
+        .await;
+    if let Err(e) = result {
+        let message = format!("Failed to delete report: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 设置测试者的信誉并重新汇总其报告过的游戏，仅管理员可用。
+#[post("/tester/reputation")]
+pub async fn tester_reputation(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<TesterRequest>,
+) -> HttpResponse {
+    if let Err(response) = require_admin(user) {
+        return response;
+    }
+    if !data.reputation.is_finite() || data.reputation < 0.0 {
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: "Reputation must be a non-negative number.",
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let data = data.into_inner();
+    let result = db
+        .transaction::<_, (), sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                let tester_active = tester::ActiveModel {
+                    name: ActiveValue::Set(data.name.clone()),
+                    reputation: ActiveValue::Set(data.reputation),
+                };
+                tester::Entity::insert(tester_active)
+                    .on_conflict(
+                        OnConflict::column(tester::Column::Name)
+                            .update_column(tester::Column::Reputation)
+                            .to_owned(),
+                    )
+                    .exec(txn)
+                    .await?;
+                let reports = report::Entity::find()
+                    .filter(report::Column::Tester.eq(data.name.as_str()))
+                    .all(txn)
+                    .await?;
+                let mut gameids: Vec<i32> = reports.into_iter().map(|r| r.gameid).collect();
+                gameids.sort_unstable();
+                gameids.dedup();
+                for gameid in gameids {
+                    aggregation::recompute(txn, gameid).await?;
+                }
+                Ok(())
+            })
+        })
+        .await;
+    if let Err(e) = result {
+        let message = format!("Failed to update tester: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/hardware/add")]
+pub async fn hardware_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<HardwareRequest>,
+) -> HttpResponse {
+    let owner = match require_login(user) {
+        Ok(owner) => owner,
+        Err(response) => return response,
+    };
+    let data = data.into_inner();
+    let hardware_active = hardware::ActiveModel {
+        cpu: ActiveValue::Set(data.cpu),
+        board: ActiveValue::Set(data.board),
+        ram: ActiveValue::Set(data.ram),
+        gpu: ActiveValue::Set(data.gpu),
+        owner: ActiveValue::Set(Some(owner)),
+        ..Default::default()
+    };
+    let result = hardware_active.insert(db.get_ref()).await;
+    if result.is_err() {
+        let message = format!("Failed to insert hardware: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = HardwareInfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        hardware: result.ok(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/hardware/info")]
+pub async fn hardware_info(
+    db: Data<DatabaseConnection>,
+    query: Query<HardwareIDQuery>,
+) -> HttpResponse {
+    let hardware = hardware::Entity::find_by_id(query.hardwareid)
+        .one(db.get_ref())
+        .await;
+    if hardware.is_err() {
+        let message = format!("Failed to query hardware: {}", hardware.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = HardwareInfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        hardware: hardware.unwrap(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/hardware/list")]
+pub async fn hardware_list(db: Data<DatabaseConnection>) -> HttpResponse {
+    let hardware = hardware::Entity::find()
+        .order_by_asc(hardware::Column::Cpu)
+        .all(db.get_ref())
+        .await;
+    if hardware.is_err() {
+        let message = format!("Failed to query hardware: {}", hardware.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = HardwareListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        hardware: hardware.unwrap(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 查询机器并确认当前用户是登记者或管理员，否则返回应直接交给客户端的响应。
+async fn find_own_hardware(
+    db: &DatabaseConnection,
+    user: Option<Identity>,
+    hardwareid: i32,
+) -> Result<hardware::Model, HttpResponse> {
+    let username = require_login(user)?;
+    let hardware = hardware::Entity::find_by_id(hardwareid).one(db).await;
+    let hardware = match hardware {
+        Ok(Some(hardware)) => hardware,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::HardwareNotFound.into(),
+                message: "Hardware not found.",
+            };
+            return Err(HttpResponse::NotFound().json(response));
+        }
+        Err(e) => {
+            let message = format!("Failed to query hardware: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return Err(HttpResponse::BadRequest().json(response));
+        }
+    };
+    if hardware.owner.as_deref() != Some(username.as_str()) && !is_admin(&username) {
+        let response = BasicResponse {
+            code: ResponseCode::PermissionDenied.into(),
+            message: "Permission denied.",
+        };
+        return Err(HttpResponse::Forbidden().json(response));
+    }
+    Ok(hardware)
+}
+
+/// 按 PATCH 语义更新机器，请求体中只需包含要修改的字段，仅登记者与管理员可用。
+#[post("/hardware/update")]
+pub async fn hardware_update(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<HardwareIDQuery>,
+    data: Json<sea_orm::JsonValue>,
+) -> HttpResponse {
+    let hardware = match find_own_hardware(db.get_ref(), user, query.hardwareid).await {
+        Ok(hardware) => hardware,
+        Err(response) => return response,
+    };
+    let hardware_active = match hardware.clone().patch(&data) {
+        Ok(hardware_active) => hardware_active,
+        Err(e) => {
+            let message = format!("Invalid update: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::InvalidRequest.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    let result = if hardware_active.is_changed() {
+        hardware_active.update(db.get_ref()).await
+    } else {
+        Ok(hardware)
+    };
+    match result {
+        Ok(hardware) => {
+            let response = HardwareInfoResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+                hardware: Some(hardware),
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to update hardware: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+/// 删除机器，仅登记者与管理员可用。
+#[post("/hardware/delete")]
+pub async fn hardware_delete(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<HardwareIDQuery>,
+) -> HttpResponse {
+    let hardware = match find_own_hardware(db.get_ref(), user, query.hardwareid).await {
+        Ok(hardware) => hardware,
+        Err(response) => return response,
+    };
+    let result = hardware.delete(db.get_ref()).await;
+    match result {
+        Ok(result) if result.rows_affected == 0 => {
+            let response = BasicResponse {
+                code: ResponseCode::HardwareNotFound.into(),
+                message: "Hardware not found.",
+            };
+            HttpResponse::NotFound().json(response)
+        }
+        Ok(_) => {
+            let response = BasicResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to delete hardware: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+#[post("/distribution/add")]
+pub async fn distribution_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<DistributionRequest>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let data = data.into_inner();
+    let distribution_active = distribution::ActiveModel {
+        name: ActiveValue::Set(data.name),
+        version: ActiveValue::Set(data.version),
+        ..Default::default()
+    };
+    let result = distribution_active.insert(db.get_ref()).await;
+    if result.is_err() {
+        let message = format!("Failed to insert distribution: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = DistributionInfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        distribution: result.ok(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/distribution/list")]
+pub async fn distribution_list(db: Data<DatabaseConnection>) -> HttpResponse {
+    let distributions = distribution::Entity::find()
+        .order_by_asc(distribution::Column::Name)
+        .all(db.get_ref())
+        .await;
+    if distributions.is_err() {
+        let message = format!(
+            "Failed to query distributions: {}",
+            distributions.err().unwrap()
+        );
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = DistributionListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        distributions: distributions.unwrap(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/layer/add")]
+pub async fn layer_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<LayerRequest>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let data = data.into_inner();
+    let layer_active = layer::ActiveModel {
+        name: ActiveValue::Set(data.name),
+        kind: ActiveValue::Set(data.kind),
+        version: ActiveValue::Set(data.version),
+        ..Default::default()
+    };
+    let result = layer_active.insert(db.get_ref()).await;
+    if result.is_err() {
+        let message = format!("Failed to insert layer: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = LayerInfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        layer: result.ok(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/layer/list")]
+pub async fn layer_list(
+    db: Data<DatabaseConnection>,
+    query: Query<LayerListQuery>,
+) -> HttpResponse {
+    let mut select = layer::Entity::find();
+    if let Some(kind) = &query.kind {
+        select = select.filter(layer::Column::Kind.eq(kind.clone()));
+    }
+    let layers = select
+        .order_by_asc(layer::Column::Name)
+        .order_by_asc(layer::Column::Version)
+        .all(db.get_ref())
+        .await;
+    if layers.is_err() {
+        let message = format!("Failed to query layers: {}", layers.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = LayerListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        layers: layers.unwrap(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 将兼容层关联到游戏，已关联时更新其作用。游戏的旧版位标志随之按必需的兼容层重新计算。
+#[post("/layer/link")]
+pub async fn layer_link(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<game_layer::Model>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let game = Entity::find_by_id(data.gameid).one(db.get_ref()).await;
+    let layer = layer::Entity::find_by_id(data.layerid)
+        .one(db.get_ref())
+        .await;
+    match (game, layer) {
+        (Ok(Some(_)), Ok(Some(_))) => {}
+        (Ok(None), _) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        (_, Ok(None)) => {
+            let response = BasicResponse {
+                code: ResponseCode::LayerNotFound.into(),
+                message: "Layer not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            let message = format!("Failed to query game or layer: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    let data = data.into_inner();
+    let gameid = data.gameid;
+    let link = data.into_active_model();
+    let result = db
+        .transaction::<_, (), sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                // 已关联时只更新作用
+                game_layer::Entity::insert(link)
+                    .on_conflict(
+                        OnConflict::columns([
+                            game_layer::Column::Gameid,
+                            game_layer::Column::Layerid,
+                        ])
+                        .update_column(game_layer::Column::Role)
+                        .to_owned(),
+                    )
+                    .exec(txn)
+                    .await?;
+                game_layer::sync_compat(txn, gameid).await?;
+                aggregation::regrade(txn, gameid).await
+            })
+        })
+        .await;
+    if result.is_err() {
+        let message = format!("Failed to link layer: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 取消游戏与兼容层的关联，游戏的旧版位标志随之按剩余的必需兼容层重新计算。
+#[post("/layer/unlink")]
+pub async fn layer_unlink(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<game_layer::Model>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let gameid = data.gameid;
+    let layerid = data.layerid;
+    let result = db
+        .transaction::<_, DeleteResult, sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                let result = game_layer::Entity::delete_by_id((gameid, layerid))
+                    .exec(txn)
+                    .await?;
+                game_layer::sync_compat(txn, gameid).await?;
+                aggregation::regrade(txn, gameid).await?;
+                Ok(result)
+            })
+        })
+        .await;
+    match result {
+        Ok(result) if result.rows_affected == 0 => {
+            let response = BasicResponse {
+                code: ResponseCode::LayerNotFound.into(),
+                message: "Layer is not linked to this game.",
+            };
+            HttpResponse::NotFound().json(response)
+        }
+        Ok(_) => {
+            let response = BasicResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to unlink layer: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+#[post("/alias/add")]
+pub async fn alias_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<AliasRequest>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let game = Entity::find_by_id(data.gameid).one(db.get_ref()).await;
+    if let Err(e) = game {
+        let message = format!("Failed to query game: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if game.unwrap().is_none() {
+        let response = BasicResponse {
+            code: ResponseCode::GameNotFound.into(),
+            message: "Game not found.",
+        };
+        return HttpResponse::NotFound().json(response);
+    }
+    let data = data.into_inner();
+    let gameid = data.gameid;
+    let result = db
+        .transaction::<_, (), sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                if data.is_primary {
+                    // 同一语言只保留一个主要名称
+                    alias::Entity::update_many()
+                        .col_expr(alias::Column::IsPrimary, false.into())
+                        .filter(alias::Column::Gameid.eq(data.gameid))
+                        .filter(match &data.language {
+                            Some(language) => alias::Column::Language.eq(language.as_str()),
+                            None => alias::Column::Language.is_null(),
+                        })
+                        .exec(txn)
+                        .await?;
+                }
+                let alias_active = alias::ActiveModel {
+                    gameid: ActiveValue::Set(data.gameid),
+                    name: ActiveValue::Set(data.name),
+                    language: ActiveValue::Set(data.language),
+                    is_primary: ActiveValue::Set(data.is_primary),
+                    ..Default::default()
+                };
+                alias_active.insert(txn).await?;
+                Ok(())
+            })
+        })
+        .await;
+    if result.is_err() {
+        let message = format!("Failed to insert alias: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let result = reindex_game(db.get_ref(), gameid).await;
+    if result.is_err() {
+        let message = format!("Failed to index alias: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::SonicDBConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/alias/delete")]
+pub async fn alias_delete(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<AliasIDQuery>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let alias = alias::Entity::find_by_id(query.aliasid)
+        .one(db.get_ref())
+        .await;
+    let alias = match alias {
+        Ok(Some(alias)) => alias,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::AliasNotFound.into(),
+                message: "Alias not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query alias: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    let gameid = alias.gameid;
+    let result = alias.delete(db.get_ref()).await;
+    if result.is_err() {
+        let message = format!("Failed to delete alias: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let result = reindex_game(db.get_ref(), gameid).await;
+    if result.is_err() {
+        let message = format!("Failed to index alias: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::SonicDBConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 为游戏登记发行平台标识，同一平台的标识已被占用时拒绝写入。
+#[post("/external/add")]
+pub async fn external_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<external_id::Model>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let game = Entity::find_by_id(data.gameid).one(db.get_ref()).await;
+    if let Err(e) = game {
+        let message = format!("Failed to query game: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if game.unwrap().is_none() {
+        let response = BasicResponse {
+            code: ResponseCode::GameNotFound.into(),
+            message: "Game not found.",
+        };
+        return HttpResponse::NotFound().json(response);
+    }
+    let existing = external_id::Entity::find_by_id((data.store.clone(), data.external_id.clone()))
+        .one(db.get_ref())
+        .await;
+    if let Err(e) = existing {
+        let message = format!("Failed to query external id: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if let Some(existing) = existing.unwrap() {
+        let message = format!(
+            "External id is already assigned to game {}.",
+            existing.gameid
+        );
+        let response = BasicResponse {
+            code: ResponseCode::ExternalIDConflict.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::Conflict().json(response);
+    }
+    let result = data
+        .into_inner()
+        .into_active_model()
+        .insert(db.get_ref())
+        .await;
+    if result.is_err() {
+        let message = format!("Failed to insert external id: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/external/delete")]
+pub async fn external_delete(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<ExternalIDQuery>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let query = query.into_inner();
+    let result = external_id::Entity::delete_by_id((query.store, query.id))
+        .exec(db.get_ref())
+        .await;
+    match result {
+        Ok(result) if result.rows_affected == 0 => {
+            let response = BasicResponse {
+                code: ResponseCode::ExternalIDNotFound.into(),
+                message: "External id not found.",
+            };
+            HttpResponse::NotFound().json(response)
+        }
+        Ok(_) => {
+            let response = BasicResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to delete external id: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+#[post("/tag/add")]
+pub async fn tag_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<TagRequest>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let data = data.into_inner();
+    let tag_active = tag::ActiveModel {
+        name: ActiveValue::Set(data.name),
+        kind: ActiveValue::Set(data.kind),
+        ..Default::default()
+    };
+    let result = tag_active.insert(db.get_ref()).await;
+    if result.is_err() {
+        let message = format!("Failed to insert tag: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = TagInfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        tag: result.ok(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/tag/list")]
+pub async fn tag_list(db: Data<DatabaseConnection>, query: Query<TagListQuery>) -> HttpResponse {
+    let mut select = tag::Entity::find();
+    if let Some(kind) = &query.kind {
+        select = select.filter(tag::Column::Kind.eq(kind.clone()));
+    }
+    let tags = select
+        .order_by_asc(tag::Column::Name)
+        .all(db.get_ref())
+        .await;
+    if tags.is_err() {
+        let message = format!("Failed to query tags: {}", tags.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = TagListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        tags: tags.unwrap(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/tag/link")]
+pub async fn tag_link(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<game_tag::Model>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let game = Entity::find_by_id(data.gameid).one(db.get_ref()).await;
+    let tag = tag::Entity::find_by_id(data.tagid).one(db.get_ref()).await;
+    match (game, tag) {
+        (Ok(Some(_)), Ok(Some(_))) => {}
+        (Ok(None), _) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        (_, Ok(None)) => {
+            let response = BasicResponse {
+                code: ResponseCode::TagNotFound.into(),
+                message: "Tag not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            let message = format!("Failed to query game or tag: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    let result = data
+        .into_inner()
+        .into_active_model()
+        .insert(db.get_ref())
+        .await;
+    if result.is_err() {
+        let message = format!("Failed to link tag: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/tag/unlink")]
+pub async fn tag_unlink(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<game_tag::Model>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let result = game_tag::Entity::delete_by_id((data.gameid, data.tagid))
+        .exec(db.get_ref())
+        .await;
+    match result {
+        Ok(result) if result.rows_affected == 0 => {
+            let response = BasicResponse {
+                code: ResponseCode::TagNotFound.into(),
+                message: "Tag is not linked to this game.",
+            };
+            HttpResponse::NotFound().json(response)
+        }
+        Ok(_) => {
+            let response = BasicResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to unlink tag: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+/// 查询游戏的全部配置指南。
+#[get("/guide")]
+pub async fn guide_list(db: Data<DatabaseConnection>, query: Query<GameIDQuery>) -> HttpResponse {
+    let guides = guide::Entity::find()
+        .filter(guide::Column::Gameid.eq(query.gameid))
+        .order_by_asc(guide::Column::Id)
+        .all(db.get_ref())
+        .await;
+    if let Err(e) = guides {
+        let message = format!("Failed to query guides: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let guides = guides.unwrap();
+    let steps = guides.load_many(guide_step::Entity, db.get_ref()).await;
+    if let Err(e) = steps {
+        let message = format!("Failed to query guide steps: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let guides = guides
+        .into_iter()
+        .zip(steps.unwrap())
+        .map(|(guide, mut steps)| {
+            steps.sort_by_key(|step| step.position);
+            GuideDetail { guide, steps }
+        })
+        .collect();
+    let response = GuideListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        guides,
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 新增配置指南。步骤中的 Markdown 会在存储前去掉原始 HTML，并渲染为清理后的 HTML。
+#[post("/guide/add")]
+pub async fn guide_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<GuideRequest>,
+) -> HttpResponse {
+    let author = match require_login(user) {
+        Ok(author) => author,
+        Err(response) => return response,
+    };
+    // 值中的换行会破坏导出的 box64rc 与启动脚本
+    if let Some((name, _)) = data
+        .env
+        .iter()
+        .find(|(name, value)| !guide::valid_env_name(name) || value.chars().any(|c| c.is_control()))
+    {
+        let message = format!("Invalid environment variable: {}", name);
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if let Some(verb) = data
+        .winetricks
+        .iter()
+        .find(|verb| !guide::valid_winetricks_verb(verb))
+    {
+        let message = format!("Invalid winetricks verb: {}", verb);
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if let Some(executable) = data
+        .executable
+        .as_deref()
+        .filter(|executable| !guide::valid_executable(executable))
+    {
+        let message = format!("Invalid executable name: {}", executable);
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    if let Some(command) = data
+        .command
+        .as_deref()
+        .filter(|command| !guide::valid_command(command))
+    {
+        let message = format!("Invalid command line: {:?}", command);
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let game = Entity::find_visible()
+        .filter(game::Column::Id.eq(data.gameid))
+        .one(db.get_ref())
+        .await;
+    match game {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    if let Some(reportid) = data.reportid {
+        let report = report::Entity::find_by_id(reportid).one(db.get_ref()).await;
+        match report {
+            Ok(Some(report)) if report.gameid == data.gameid => {}
+            Ok(_) => {
+                let response = BasicResponse {
+                    code: ResponseCode::ReportNotFound.into(),
+                    message: "Report not found.",
+                };
+                return HttpResponse::NotFound().json(response);
+            }
+            Err(e) => {
+                let message = format!("Failed to query report: {}", e);
+                let response = BasicResponse {
+                    code: ResponseCode::DatabaseConnectionError.into(),
+                    message: message.as_str(),
+                };
+                return HttpResponse::BadRequest().json(response);
+            }
+        }
+    }
+    let data = data.into_inner();
+    let guide_active = guide::ActiveModel {
+        gameid: ActiveValue::Set(data.gameid),
+        reportid: ActiveValue::Set(data.reportid),
+        author: ActiveValue::Set(author),
+        title: ActiveValue::Set(data.title),
+        wine_prefix: ActiveValue::Set(data.wine_prefix),
+        env: ActiveValue::Set(guide::EnvVars(data.env)),
+        winetricks: ActiveValue::Set(guide::WinetricksVerbs(data.winetricks)),
+        executable: ActiveValue::Set(data.executable),
+        command: ActiveValue::Set(data.command.map(guide::CommandArgs)),
+        created_at: ActiveValue::Set(chrono::Utc::now()),
+        ..Default::default()
+    };
+    let steps = data.steps;
+    let result = db
+        .transaction::<_, GuideDetail, sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                let guide = guide_active.insert(txn).await?;
+                let mut inserted = Vec::new();
+                for (position, body) in steps.iter().enumerate() {
+                    let body = markdown::sanitize(body);
+                    let step = guide_step::ActiveModel {
+                        guideid: ActiveValue::Set(guide.id),
+                        position: ActiveValue::Set(position as i32),
+                        body_html: ActiveValue::Set(markdown::render(&body)),
+                        body: ActiveValue::Set(body),
+                    };
+                    inserted.push(step.insert(txn).await?);
+                }
+                Ok(GuideDetail {
+                    guide,
+                    steps: inserted,
+                })
+            })
+        })
+        .await;
+    match result {
+        Ok(guide) => {
+            let response = GuideInfoResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+                guide: Some(guide),
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to insert guide: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+/// 删除配置指南，仅指南作者或管理员可用。
+#[post("/guide/delete")]
+pub async fn guide_delete(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    query: Query<GuideIDQuery>,
+) -> HttpResponse {
+    let username = match require_login(user) {
+        Ok(username) => username,
+        Err(response) => return response,
+    };
+    let guide = guide::Entity::find_by_id(query.guideid)
+        .one(db.get_ref())
+        .await;
+    let guide = match guide {
+        Ok(Some(guide)) => guide,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::GuideNotFound.into(),
+                message: "Guide not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query guide: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    if guide.author != username && !is_admin(&username) {
+        let response = BasicResponse {
+            code: ResponseCode::PermissionDenied.into(),
+            message: "Permission denied.",
+        };
+        return HttpResponse::Forbidden().json(response);
+    }
+    // 步骤通过外键级联删除
+    let result = guide.delete(db.get_ref()).await;
+    if let Err(e) = result {
+        let message = format!("Failed to delete guide: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 按游戏所需的兼容层与指南导出 box64rc、启动脚本或 Lutris 安装脚本。
+#[get("/export")]
+pub async fn export_game(db: Data<DatabaseConnection>, query: Query<ExportQuery>) -> HttpResponse {
+    let game = Entity::find_visible()
+        .filter(game::Column::Id.eq(query.gameid))
+        .one(db.get_ref())
+        .await;
+    let game = match game {
+        Ok(Some(game)) => game,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    let mut select = guide::Entity::find().filter(guide::Column::Gameid.eq(game.id));
+    if let Some(guideid) = query.guideid {
+        select = select.filter(guide::Column::Id.eq(guideid));
+    }
+    let guide = select
+        .order_by_desc(guide::Column::Id)
+        .one(db.get_ref())
+        .await;
+    let guide = match guide {
+        Ok(guide) => guide,
+        Err(e) => {
+            let message = format!("Failed to query guide: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    if query.guideid.is_some() && guide.is_none() {
+        let response = BasicResponse {
+            code: ResponseCode::GuideNotFound.into(),
+            message: "Guide not found.",
+        };
+        return HttpResponse::NotFound().json(response);
+    }
+    // 旧版位标志在关联兼容层时已按必需的兼容层同步
+    let result = query.format.render(&game, &game.compat, guide.as_ref());
+    match result {
+        Ok(body) => HttpResponse::Ok()
+            .content_type(query.format.content_type())
+            .insert_header((
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", query.format.file_name(&game)),
+            ))
+            .body(body),
+        Err(e) => {
+            let message = format!("Failed to export game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::SystemInternalError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+/// 新增已知问题。
+#[post("/issue/add")]
+pub async fn issue_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<IssueRequest>,
+) -> HttpResponse {
+    let reporter = match require_login(user) {
+        Ok(reporter) => reporter,
+        Err(response) => return response,
+    };
+    if data
+        .bug_url
+        .as_deref()
+        .is_some_and(|url| !issue::valid_bug_url(url))
+    {
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: "Bug URL must be an http or https link.",
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let game = Entity::find_visible()
+        .filter(game::Column::Id.eq(data.gameid))
+        .one(db.get_ref())
+        .await;
+    match game {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    let layers = layer::Entity::find()
+        .filter(layer::Column::Id.is_in(data.layers.clone()))
+        .all(db.get_ref())
+        .await;
+    if let Err(e) = layers {
+        let message = format!("Failed to query layers: {}", e);
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let layers = layers.unwrap();
+    if data
+        .layers
+        .iter()
+        .any(|id| !layers.iter().any(|l| l.id == *id))
+    {
+        let response = BasicResponse {
+            code: ResponseCode::LayerNotFound.into(),
+            message: "Layer not found.",
+        };
+        return HttpResponse::NotFound().json(response);
+    }
+    let data = data.into_inner();
+    let issue_active = issue::ActiveModel {
+        gameid: ActiveValue::Set(data.gameid),
+        reporter: ActiveValue::Set(reporter),
+        title: ActiveValue::Set(data.title),
+        severity: ActiveValue::Set(data.severity),
+        workaround: ActiveValue::Set(data.workaround.map(|text| markdown::sanitize(&text))),
+        bug_url: ActiveValue::Set(data.bug_url),
+        fixed_in: ActiveValue::Set(data.fixed_in),
+        created_at: ActiveValue::Set(chrono::Utc::now()),
+        ..Default::default()
+    };
+    let result = db
+        .transaction::<_, IssueDetail, sea_orm::DbErr>(|txn| {
+            Box::pin(async move {
+                let issue = issue_active.insert(txn).await?;
+                if !layers.is_empty() {
+                    issue_layer::Entity::insert_many(layers.iter().map(|layer| {
+                        issue_layer::ActiveModel {
+                            issueid: ActiveValue::Set(issue.id),
+                            layerid: ActiveValue::Set(layer.id),
+                        }
+                    }))
+                    .exec(txn)
+                    .await?;
+                }
+                Ok(IssueDetail { issue, layers })
+            })
+        })
+        .await;
+    match result {
+        Ok(issue) => {
+            let response = IssueInfoResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+                issue: Some(issue),
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to insert issue: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+/// 查询游戏的已知问题，默认只返回尚未解决的问题。
+#[get("/issue/list")]
+pub async fn issue_list(
+    db: Data<DatabaseConnection>,
+    query: Query<IssueListQuery>,
+) -> HttpResponse {
+    let select = if query.all {
+        issue::Entity::find()
+    } else {
+        issue::Entity::find_open()
+    };
+    let issues = select
+        .filter(issue::Column::Gameid.eq(query.gameid))
+        .order_by_asc(issue::Column::Severity)
+        .all(db.get_ref())
+        .await;
+    let issues = match issues {
+        Ok(issues) => issue_details(db.get_ref(), issues).await,
+        Err(e) => Err(e),
+    };
+    match issues {
+        Ok(issues) => {
+            let response = IssueListResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+                issues,
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to query issues: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+/// 记录问题的修复版本，或清空修复版本以重新打开问题，仅报告者与管理员可用。
+#[post("/issue/fix")]
+pub async fn issue_fix(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<IssueFixRequest>,
+) -> HttpResponse {
+    let username = match require_login(user) {
+        Ok(username) => username,
+        Err(response) => return response,
+    };
+    let data = data.into_inner();
+    let issue = issue::Entity::find_by_id(data.issueid)
+        .one(db.get_ref())
+        .await;
+    let issue = match issue {
+        Ok(Some(issue)) => issue,
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::IssueNotFound.into(),
+                message: "Issue not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query issue: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    };
+    if issue.reporter != username && !is_admin(&username) {
+        let response = BasicResponse {
+            code: ResponseCode::PermissionDenied.into(),
+            message: "Permission denied.",
+        };
+        return HttpResponse::Forbidden().json(response);
+    }
+    let mut issue_active = issue.into_active_model();
+    issue_active.fixed_in = ActiveValue::Set(data.fixed_in);
+    let result = issue_active.update(db.get_ref()).await;
+    match result {
+        Ok(_) => {}
+        Err(e) => {
+            let message = format!("Failed to update issue: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
     let response = BasicResponse {
-        code: ResponseCode::NotImplemented.into(),
-        message: "Not implemented.",
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 新增运行库。
+#[post("/dependency/add")]
+pub async fn dependency_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<DependencyRequest>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    if data
+        .winetricks
+        .as_deref()
+        .is_some_and(|verb| !guide::valid_winetricks_verb(verb))
+    {
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: "Invalid winetricks verb.",
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let data = data.into_inner();
+    let dependency_active = dependency::ActiveModel {
+        name: ActiveValue::Set(data.name),
+        kind: ActiveValue::Set(data.kind),
+        winetricks: ActiveValue::Set(data.winetricks),
+        ..Default::default()
+    };
+    let result = dependency_active.insert(db.get_ref()).await;
+    if result.is_err() {
+        let message = format!("Failed to insert dependency: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = DependencyInfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        dependency: result.ok(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/dependency/list")]
+pub async fn dependency_list(
+    db: Data<DatabaseConnection>,
+    query: Query<DependencyListQuery>,
+) -> HttpResponse {
+    let mut select = dependency::Entity::find();
+    if let Some(kind) = &query.kind {
+        select = select.filter(dependency::Column::Kind.eq(kind.clone()));
+    }
+    let dependencies = select
+        .order_by_asc(dependency::Column::Name)
+        .all(db.get_ref())
+        .await;
+    if dependencies.is_err() {
+        let message = format!(
+            "Failed to query dependencies: {}",
+            dependencies.err().unwrap()
+        );
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = DependencyListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        dependencies: dependencies.unwrap(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+/// 将运行库关联到游戏，已关联时更新版本要求。
+#[post("/dependency/link")]
+pub async fn dependency_link(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<game_dependency::Model>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    if data
+        .version
+        .as_deref()
+        .is_some_and(|version| !game_dependency::valid_constraint(version))
+    {
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: "Invalid version constraint.",
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let game = Entity::find_by_id(data.gameid).one(db.get_ref()).await;
+    let dependency = dependency::Entity::find_by_id(data.dependencyid)
+        .one(db.get_ref())
+        .await;
+    match (game, dependency) {
+        (Ok(Some(_)), Ok(Some(_))) => {}
+        (Ok(None), _) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        (_, Ok(None)) => {
+            let response = BasicResponse {
+                code: ResponseCode::DependencyNotFound.into(),
+                message: "Dependency not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            let message = format!("Failed to query game or dependency: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    let link = data.into_inner().into_active_model();
+    // 已关联时只更新版本要求
+    let result = game_dependency::Entity::insert(link)
+        .on_conflict(
+            OnConflict::columns([
+                game_dependency::Column::Gameid,
+                game_dependency::Column::Dependencyid,
+            ])
+            .update_column(game_dependency::Column::Version)
+            .to_owned(),
+        )
+        .exec(db.get_ref())
+        .await;
+    if result.is_err() {
+        let message = format!("Failed to link dependency: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BasicResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[post("/dependency/unlink")]
+pub async fn dependency_unlink(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<game_dependency::Model>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    let result = game_dependency::Entity::delete_by_id((data.gameid, data.dependencyid))
+        .exec(db.get_ref())
+        .await;
+    match result {
+        Ok(result) if result.rows_affected == 0 => {
+            let response = BasicResponse {
+                code: ResponseCode::DependencyNotFound.into(),
+                message: "Dependency is not linked to this game.",
+            };
+            HttpResponse::NotFound().json(response)
+        }
+        Ok(_) => {
+            let response = BasicResponse {
+                code: ResponseCode::Success.into(),
+                message: "OK",
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let message = format!("Failed to unlink dependency: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            HttpResponse::BadRequest().json(response)
+        }
+    }
+}
+
+/// 登记游戏版本。
+#[post("/build/add")]
+pub async fn build_add(
+    db: Data<DatabaseConnection>,
+    user: Option<Identity>,
+    data: Json<BuildRequest>,
+) -> HttpResponse {
+    if let Err(response) = require_login(user) {
+        return response;
+    }
+    if data
+        .exe_sha256
+        .as_deref()
+        .is_some_and(|hash| !build::valid_sha256(hash))
+    {
+        let response = BasicResponse {
+            code: ResponseCode::InvalidRequest.into(),
+            message: "Executable hash must be a hex-encoded SHA-256.",
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let game = Entity::find_visible()
+        .filter(game::Column::Id.eq(data.gameid))
+        .one(db.get_ref())
+        .await;
+    match game {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            let response = BasicResponse {
+                code: ResponseCode::GameNotFound.into(),
+                message: "Game not found.",
+            };
+            return HttpResponse::NotFound().json(response);
+        }
+        Err(e) => {
+            let message = format!("Failed to query game: {}", e);
+            let response = BasicResponse {
+                code: ResponseCode::DatabaseConnectionError.into(),
+                message: message.as_str(),
+            };
+            return HttpResponse::BadRequest().json(response);
+        }
+    }
+    let data = data.into_inner();
+    let build_active = build::ActiveModel {
+        gameid: ActiveValue::Set(data.gameid),
+        version: ActiveValue::Set(data.version),
+        edition: ActiveValue::Set(data.edition),
+        released_on: ActiveValue::Set(data.released_on),
+        exe_sha256: ActiveValue::Set(data.exe_sha256.map(|hash| hash.to_lowercase())),
+        ..Default::default()
+    };
+    let result = build_active.insert(db.get_ref()).await;
+    if result.is_err() {
+        let message = format!("Failed to insert build: {}", result.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BuildInfoResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        build: result.ok(),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+#[get("/build/list")]
+pub async fn build_list(db: Data<DatabaseConnection>, query: Query<GameIDQuery>) -> HttpResponse {
+    let builds = find_builds(db.get_ref(), query.gameid).await;
+    if builds.is_err() {
+        let message = format!("Failed to query builds: {}", builds.err().unwrap());
+        let response = BasicResponse {
+            code: ResponseCode::DatabaseConnectionError.into(),
+            message: message.as_str(),
+        };
+        return HttpResponse::BadRequest().json(response);
+    }
+    let response = BuildListResponse {
+        code: ResponseCode::Success.into(),
+        message: "OK",
+        builds: builds.unwrap(),
     };
-    HttpResponse::NotImplemented().json(response)
+    HttpResponse::Ok().json(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::TestDatabase;
+    use crate::entity::distribution;
+    use crate::entity::game;
+    use crate::entity::game_layer;
+    use crate::entity::hardware;
+    use crate::entity::layer;
+    use crate::entity::report;
+    use crate::fixtures;
+    use actix_identity::{Identity, IdentityMiddleware};
+    use actix_session::{storage::CookieSessionStore, SessionMiddleware};
+    use actix_web::cookie::Key;
+    use actix_web::test::{self, TestRequest};
+    use actix_web::web::{self, Data};
+    use actix_web::{App, HttpMessage, HttpRequest, HttpResponse};
+    use enumflags2::make_bitflags;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::DatabaseConnection;
+    use sea_orm::EntityTrait;
+
+    use crate::entity::game::Abi;
+    use crate::entity::game::CompatibilityLayerItem;
+
+    use super::game_list;
+    use super::layer_link;
+    use super::layer_unlink;
+    use super::report_filter;
+    use super::retain_reported;
+    use super::update;
+
+    /// 测试用的登录入口，以固定用户名登录。
+    async fn login(request: HttpRequest) -> HttpResponse {
+        Identity::login(&request.extensions(), "tester".to_string()).unwrap();
+        HttpResponse::Ok().finish()
+    }
+
+    /// 建立带会话的测试应用并注册给定的接口，以 `tester` 登录后返回应用及会话 Cookie。
+    macro_rules! app {
+        ($db:expr, $($service:expr),+) => {{
+            let app = test::init_service(
+                App::new()
+                    .app_data($db)
+                    .wrap(IdentityMiddleware::default())
+                    .wrap(SessionMiddleware::new(
+                        CookieSessionStore::default(),
+                        Key::from(&[0; 64]),
+                    ))
+                    .route("/login", web::post().to(login))
+                    $(.service($service))+,
+            )
+            .await;
+            let request = TestRequest::post().uri("/login").to_request();
+            let response = test::call_service(&app, request).await;
+            let cookie = response.response().cookies().next().unwrap().into_owned();
+            (app, cookie)
+        }};
+    }
+
+    /// 游戏 `gameid` 的一份报告，尚未写入数据库，可用结构体更新语法修改其他字段。
+    fn report(gameid: i32) -> report::ActiveModel {
+        report::ActiveModel {
+            gameid: ActiveValue::Set(gameid),
+            tester: ActiveValue::Set("bob".to_string()),
+            supportlevel: ActiveValue::Set(game::SupportLevel::GOOD),
+            compat: ActiveValue::Set(game::Compatibility::default()),
+            created_at: ActiveValue::Set(chrono::Utc::now()),
+            ..Default::default()
+        }
+    }
+
+    /// 请求 `/games` 并返回列出的游戏 id。
+    macro_rules! list_ids {
+        ($app:expr, $uri:expr) => {{
+            let request = TestRequest::get().uri($uri).to_request();
+            let body: serde_json::Value = test::call_and_read_body_json($app, request).await;
+            body["games"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|game| game["id"].as_i64().unwrap())
+                .collect::<Vec<_>>()
+        }};
+    }
+
+    #[tokio::test]
+    async fn update_protected_fields() {
+        let (db, _guard) = fixtures::setup().await;
+        fixtures::game(1).insert(&db).await.unwrap();
+        let db = Data::new(db);
+        let (app, cookie) = app!(db.clone(), update);
+        let patch = |body: serde_json::Value| {
+            TestRequest::post()
+                .uri("/update?gameid=1")
+                .cookie(cookie.clone())
+                .set_json(body)
+                .to_request()
+        };
+
+        // 尚无报告时可以手动填写运行效果
+        let response = test::call_service(
+            &app,
+            patch(serde_json::json!({ "supportlevel": "PERFECT" })),
+        )
+        .await;
+        assert!(response.status().is_success());
+        let response =
+            test::call_service(&app, patch(serde_json::json!({ "confidence": 1.0 }))).await;
+        assert!(response.status().is_client_error());
+        let game = game::Entity::find_by_id(1)
+            .one(db.get_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(game.supportlevel, game::SupportLevel::PERFECT);
+        assert_eq!(game.confidence, None);
+
+        report(1).insert(db.get_ref()).await.unwrap();
+        let response =
+            test::call_service(&app, patch(serde_json::json!({ "supportlevel": "FAIL" }))).await;
+        assert!(response.status().is_client_error());
+        // 原样提交未改动的字段不算错误
+        let response = test::call_service(
+            &app,
+            patch(serde_json::json!({ "id": 1, "supportlevel": "PERFECT", "compat": 8 })),
+        )
+        .await;
+        assert!(response.status().is_success());
+        let game = game::Entity::find_by_id(1)
+            .one(db.get_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(game.supportlevel, game::SupportLevel::PERFECT);
+        assert_eq!(game.compat.0.bits(), 8);
+    }
+
+    #[tokio::test]
+    async fn layer_link_syncs_compat() {
+        let (db, _guard) = fixtures::setup().await;
+        fixtures::game(1).insert(&db).await.unwrap();
+        for (name, kind) in [
+            ("wine", layer::LayerKind::WINE),
+            ("box64", layer::LayerKind::TRANSLATOR),
+        ] {
+            let layer = layer::ActiveModel {
+                name: ActiveValue::Set(name.to_string()),
+                kind: ActiveValue::Set(kind),
+                ..Default::default()
+            };
+            layer.insert(&db).await.unwrap();
+        }
+        let db = Data::new(db);
+        let (app, cookie) = app!(db.clone(), layer_link, layer_unlink);
+        let call = |uri: &str, layerid: i32, role: game_layer::LayerRole| {
+            let link = game_layer::Model {
+                gameid: 1,
+                layerid,
+                role,
+            };
+            TestRequest::post()
+                .uri(uri)
+                .cookie(cookie.clone())
+                .set_json(link)
+                .to_request()
+        };
+        let compat = || async {
+            let game = game::Entity::find_by_id(1).one(db.get_ref()).await.unwrap();
+            game.unwrap().compat.0
+        };
+
+        for layerid in [1, 2] {
+            let request = call("/layer/link", layerid, game_layer::LayerRole::REQUIRED);
+            assert!(test::call_service(&app, request)
+                .await
+                .status()
+                .is_success());
+        }
+        assert_eq!(
+            compat().await,
+            make_bitflags!(CompatibilityLayerItem::{WINE | BOX64})
+        );
+        // 改为非必需后清除对应的位
+        let request = call("/layer/link", 2, game_layer::LayerRole::TESTED);
+        assert!(test::call_service(&app, request)
+            .await
+            .status()
+            .is_success());
+        assert_eq!(
+            compat().await,
+            make_bitflags!(CompatibilityLayerItem::{WINE})
+        );
+        // 取消关联后同样清除
+        let request = call("/layer/unlink", 1, game_layer::LayerRole::REQUIRED);
+        assert!(test::call_service(&app, request)
+            .await
+            .status()
+            .is_success());
+        assert!(compat().await.is_empty());
+        let request = call("/layer/unlink", 1, game_layer::LayerRole::REQUIRED);
+        assert!(test::call_service(&app, request)
+            .await
+            .status()
+            .is_client_error());
+    }
+
+    #[tokio::test]
+    async fn game_list_cpu_filter() {
+        let (db, _guard) = fixtures::setup().await;
+        for (id, cpu) in [(1, "3A6000"), (2, "3A5000")] {
+            fixtures::game(id).insert(&db).await.unwrap();
+            let hardware = hardware::ActiveModel {
+                cpu: ActiveValue::Set(cpu.to_string()),
+                ..Default::default()
+            };
+            let hardware = hardware.insert(&db).await.unwrap();
+            let report = report::ActiveModel {
+                hardwareid: ActiveValue::Set(Some(hardware.id)),
+                ..report(id)
+            };
+            report.insert(&db).await.unwrap();
+        }
+        fixtures::game(3).insert(&db).await.unwrap();
+        let (app, _) = app!(Data::new(db), game_list);
+
+        assert_eq!(list_ids!(&app, "/games"), vec![1, 2, 3]);
+        assert_eq!(list_ids!(&app, "/games?cpu=3A6000"), vec![1]);
+        assert!(list_ids!(&app, "/games?cpu=2K2000").is_empty());
+    }
+
+    /// 游戏 1 有一份新世界、AOSC OS 上的报告，游戏 2 有一份旧世界的报告，游戏 3 没有报告。
+    async fn reported_games() -> (DatabaseConnection, TestDatabase) {
+        let (db, guard) = fixtures::setup().await;
+        let distribution = distribution::ActiveModel {
+            name: ActiveValue::Set("AOSC OS".to_string()),
+            version: ActiveValue::Set(None),
+            ..Default::default()
+        };
+        let distribution = distribution.insert(&db).await.unwrap();
+        for id in [1, 2, 3] {
+            fixtures::game(id).insert(&db).await.unwrap();
+        }
+        let reports = [
+            report::ActiveModel {
+                abi: ActiveValue::Set(Some(Abi::NEWWORLD)),
+                distributionid: ActiveValue::Set(Some(distribution.id)),
+                ..report(1)
+            },
+            report::ActiveModel {
+                abi: ActiveValue::Set(Some(Abi::OLDWORLD)),
+                ..report(2)
+            },
+        ];
+        for report in reports {
+            report.insert(&db).await.unwrap();
+        }
+        (db, guard)
+    }
+
+    #[tokio::test]
+    async fn game_list_abi_filter() {
+        let (db, _guard) = reported_games().await;
+        let (app, _) = app!(Data::new(db), game_list);
+        assert_eq!(list_ids!(&app, "/games?abi=NEWWORLD"), vec![1]);
+        assert_eq!(list_ids!(&app, "/games?abi=OLDWORLD"), vec![2]);
+        assert!(list_ids!(&app, "/games?abi=LIBLOL").is_empty());
+    }
+
+    #[tokio::test]
+    async fn game_list_distribution_filter() {
+        let (db, _guard) = reported_games().await;
+        let (app, _) = app!(Data::new(db), game_list);
+        assert_eq!(list_ids!(&app, "/games?distributionid=1"), vec![1]);
+        // 条件须由同一份报告满足
+        assert!(list_ids!(&app, "/games?distributionid=1&abi=OLDWORLD").is_empty());
+        assert!(list_ids!(&app, "/games?distributionid=2").is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_report_filters() {
+        let (db, _guard) = reported_games().await;
+        // 搜索结果按相关度排序，筛选后保持原有顺序
+        let mut ids = vec![3, 2, 1];
+        let reports = report_filter(None, Some(&Abi::OLDWORLD), None).unwrap();
+        retain_reported(&db, &mut ids, reports).await.unwrap();
+        assert_eq!(ids, vec![2]);
+
+        let mut ids = vec![3, 2, 1];
+        let reports = report_filter(None, None, Some(1)).unwrap();
+        retain_reported(&db, &mut ids, reports).await.unwrap();
+        assert_eq!(ids, vec![1]);
+        assert!(report_filter(None, None, None).is_none());
+    }
 }
@@ -0,0 +1,595 @@
+use crate::entity::game::{self, Compatibility, SupportLevel};
+use crate::entity::{build, game_layer, game_stack, layer, report, report_layer, tester};
+use chrono::{DateTime, Utc};
+use config::{Config, ConfigError};
+use lazy_static::lazy_static;
+use log::warn;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait,
+    IntoActiveModel, LoaderTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{RwLock, RwLockReadGuard};
+
+lazy_static! {
+    static ref RULES: RwLock<AggregationRules> = RwLock::new(load().unwrap_or_else(|e| {
+        warn!("Failed to load aggregation rules, using defaults: {}", e);
+        AggregationRules::default()
+    }));
+}
+
+/// 汇总报告的规则，对应设置项 `[AGGREGATION]`。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AggregationRules {
+    /// 报告权重减半所需的天数。
+    pub half_life_days: f64,
+    /// 报告所用兼容层不是登记表中的最新版本时，权重乘以该系数。
+    pub outdated_layer_factor: f64,
+    /// 总权重达到该值时，置信度仅由报告间的一致程度决定。
+    pub confident_weight: f64,
+}
+
+impl Default for AggregationRules {
+    fn default() -> Self {
+        AggregationRules {
+            half_life_days: 180.0,
+            outdated_layer_factor: 0.5,
+            confident_weight: 3.0,
+        }
+    }
+}
+
+impl AggregationRules {
+    /// 单份报告的权重：随时间衰减，乘以测试者信誉，使用旧版兼容层时再打折扣。
+    pub fn weight(
+        &self,
+        report: &report::Model,
+        reputation: f64,
+        outdated: bool,
+        now: DateTime<Utc>,
+    ) -> f64 {
+        let age = (now - report.created_at).num_seconds().max(0) as f64 / 86400.0;
+        let recency = 0.5_f64.powf(age / self.half_life_days);
+        let layer = if outdated {
+            self.outdated_layer_factor
+        } else {
+            1.0
+        };
+        recency * reputation.max(0.0) * layer
+    }
+
+    /// 由带权重的运行效果得出共识与置信度。共识取加权中位数，置信度为与共识一致的
+    /// 权重占比，总权重不足 `confident_weight` 时按比例降低。
+    pub fn consensus(&self, samples: &[(SupportLevel, f64)]) -> Option<(SupportLevel, f64)> {
+        let total: f64 = samples.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut sorted: Vec<&(SupportLevel, f64)> = samples.iter().collect();
+        sorted.sort_by_key(|(level, _)| level.clone() as u8);
+        let mut accumulated = 0.0;
+        let mut median = sorted[0].0.clone();
+        for (level, weight) in sorted {
+            accumulated += weight;
+            if accumulated * 2.0 >= total {
+                median = level.clone();
+                break;
+            }
+        }
+        let agreeing: f64 = samples
+            .iter()
+            .filter(|(level, _)| *level == median)
+            .map(|(_, weight)| weight)
+            .sum();
+        let coverage = (total / self.confident_weight).min(1.0);
+        Some((median, agreeing / total * coverage))
+    }
+}
+
+/// 从 settings.toml 读取汇总规则。
+fn load() -> Result<AggregationRules, ConfigError> {
+    Config::builder()
+        .add_source(config::File::with_name("settings.toml"))
+        .build()?
+        .get::<AggregationRules>("AGGREGATION")
+}
+
+/// 当前生效的汇总规则。
+pub fn rules() -> RwLockReadGuard<'static, AggregationRules> {
+    RULES.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 重新读取 settings.toml 中的汇总规则。已汇总的结果不会自动更新，需再调用 [`recompute_all`]。
+pub fn reload() -> Result<AggregationRules, ConfigError> {
+    let rules = load()?;
+    *RULES.write().unwrap_or_else(|e| e.into_inner()) = rules.clone();
+    Ok(rules)
+}
+
+/// 重新计算评级排序键时每批处理的游戏数。
+const REGRADE_BATCH: u64 = 500;
+
+/// 带权重的运行效果。
+type Samples = Vec<(SupportLevel, f64)>;
+
+/// 按版本号中的数字逐段比较，非数字的部分忽略。
+pub fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+/// 由全部报告重新计算游戏的运行效果与置信度并缓存到 `games` 表，同时按兼容层组合分别汇总写入
+/// `game_stacks` 表，评级最好的组合另存到 `best_supportlevel`、`best_confidence`；`games.compat` 不会改动。
+/// 指明了游戏版本的报告另按版本汇总到 `builds` 表。没有报告时保留原有的运行效果，仅清空置信度。
+pub async fn recompute<C: ConnectionTrait>(db: &C, gameid: i32) -> Result<(), DbErr> {
+    let Some(game) = game::Entity::find_by_id(gameid).one(db).await? else {
+        return Err(DbErr::RecordNotFound("Game not found.".to_string()));
+    };
+    let reports = report::Entity::find()
+        .filter(report::Column::Gameid.eq(gameid))
+        .all(db)
+        .await?;
+    let layers = reports
+        .load_many_to_many(layer::Entity, report_layer::Entity, db)
+        .await?;
+    let mut latest: HashMap<String, Vec<u64>> = HashMap::new();
+    for layer in layer::Entity::find().all(db).await? {
+        let version = version_key(layer.version.as_deref().unwrap_or_default());
+        let entry = latest.entry(layer.name.to_lowercase()).or_default();
+        if version > *entry {
+            *entry = version;
+        }
+    }
+    let reputations: HashMap<String, f64> = tester::Entity::find()
+        .filter(tester::Column::Name.is_in(reports.iter().map(|r| r.tester.clone())))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|tester| (tester.name, tester.reputation))
+        .collect();
+
+    // 汇总期间规则可能被重新加载，复制一份以免跨越 await 持有锁
+    let rules = rules().clone();
+    let now = Utc::now();
+    // 按报告关联的兼容层分别汇总；未关联登记表的旧报告只能按位标志区分
+    let mut samples: BTreeMap<(String, u32), (Compatibility, Samples)> = BTreeMap::new();
+    let mut build_samples: HashMap<i32, Samples> = HashMap::new();
+    let mut all_samples = Samples::new();
+    for (report, layers) in reports.iter().zip(layers) {
+        let outdated = layers.iter().any(|layer| {
+            let version = version_key(layer.version.as_deref().unwrap_or_default());
+            latest
+                .get(&layer.name.to_lowercase())
+                .is_some_and(|latest| version < *latest)
+        });
+        let reputation = reputations.get(&report.tester).copied().unwrap_or(1.0);
+        let weight = rules.weight(report, reputation, outdated, now);
+        let key = game_stack::layer_key(&layers);
+        let bits = if key.is_empty() {
+            report.compat.0.bits()
+        } else {
+            0
+        };
+        let (compat, entry) = samples.entry((key, bits)).or_default();
+        compat.0 |= report.compat.0;
+        entry.push((report.supportlevel.clone(), weight));
+        all_samples.push((report.supportlevel.clone(), weight));
+        if let Some(buildid) = report.buildid {
+            build_samples
+                .entry(buildid)
+                .or_default()
+                .push((report.supportlevel.clone(), weight));
+        }
+    }
+
+    let builds = build::Entity::find()
+        .filter(build::Column::Gameid.eq(gameid))
+        .all(db)
+        .await?;
+    for build in builds {
+        let consensus = build_samples
+            .get(&build.id)
+            .and_then(|samples| rules.consensus(samples));
+        let mut active = build.into_active_model();
+        active.supportlevel = ActiveValue::Set(consensus.as_ref().map(|(level, _)| level.clone()));
+        active.confidence = ActiveValue::Set(consensus.map(|(_, confidence)| confidence));
+        active.update(db).await?;
+    }
+
+    game_stack::Entity::delete_many()
+        .filter(game_stack::Column::Gameid.eq(gameid))
+        .exec(db)
+        .await?;
+    let mut stacks = Vec::new();
+    for ((layers, _), (compat, samples)) in samples {
+        let Some((supportlevel, confidence)) = rules.consensus(&samples) else {
+            continue;
+        };
+        let stack = game_stack::ActiveModel {
+            gameid: ActiveValue::Set(gameid),
+            layers: ActiveValue::Set(layers),
+            compat: ActiveValue::Set(compat),
+            supportlevel: ActiveValue::Set(supportlevel),
+            confidence: ActiveValue::Set(confidence),
+        };
+        stacks.push(stack.insert(db).await?);
+    }
+
+    // 游戏自身的运行效果取全部报告的汇总结果，评级最好的组合另外保存
+    let links = game_layer::find_links(db, [gameid]).await?;
+    let grade = game.grading_with(&stacks, &links);
+    let best = stacks
+        .into_iter()
+        .min_by_key(|stack| game::grade_order(&stack.grading(&links)));
+    let mut active = game.into_active_model();
+    active.grade_rank = ActiveValue::Set(Some(game::grade_rank(&grade)));
+    match rules.consensus(&all_samples) {
+        Some((supportlevel, confidence)) => {
+            active.supportlevel = ActiveValue::Set(supportlevel);
+            active.confidence = ActiveValue::Set(Some(confidence));
+        }
+        None => active.confidence = ActiveValue::Set(None),
+    }
+    active.best_supportlevel =
+        ActiveValue::Set(best.as_ref().map(|stack| stack.supportlevel.clone()));
+    active.best_confidence = ActiveValue::Set(best.map(|stack| stack.confidence));
+    active.update(db).await?;
+    Ok(())
+}
+
+/// 按当前规则重新汇总全部游戏，包括已删除的游戏。返回处理的游戏数。
+pub async fn recompute_all<C: ConnectionTrait>(db: &C) -> Result<usize, DbErr> {
+    let ids: Vec<i32> = game::Entity::find()
+        .select_only()
+        .column(game::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+    for id in &ids {
+        recompute(db, *id).await?;
+    }
+    Ok(ids.len())
+}
+
+/// 按当前评级规则重新计算游戏的评级排序键，游戏自身的字段或兼容层关联变化后调用。
+pub async fn regrade<C: ConnectionTrait>(db: &C, gameid: i32) -> Result<(), DbErr> {
+    regrade_games(db, Condition::all().add(game::Column::Id.eq(gameid))).await?;
+    Ok(())
+}
+
+/// 重新计算全部游戏的评级排序键，评级规则变化后调用。`missing` 为真时只处理尚未计算的游戏。
+/// 返回处理的游戏数。
+pub async fn regrade_all<C: ConnectionTrait>(db: &C, missing: bool) -> Result<usize, DbErr> {
+    let condition = if missing {
+        Condition::all().add(game::Column::GradeRank.is_null())
+    } else {
+        Condition::all()
+    };
+    regrade_games(db, condition).await
+}
+
+async fn regrade_games<C: ConnectionTrait>(db: &C, condition: Condition) -> Result<usize, DbErr> {
+    let mut count = 0;
+    let mut last = None;
+    // 按 id 分批处理，以免一次取出全部游戏，也避免 `IN` 子句中的参数过多
+    loop {
+        let mut select = game::Entity::find().filter(condition.clone());
+        if let Some(last) = last {
+            select = select.filter(game::Column::Id.gt(last));
+        }
+        let games = select
+            .order_by_asc(game::Column::Id)
+            .limit(REGRADE_BATCH)
+            .all(db)
+            .await?;
+        let Some(game) = games.last() else {
+            return Ok(count);
+        };
+        last = Some(game.id);
+        count += games.len();
+        let stacks = games.load_many(game_stack::Entity, db).await?;
+        let links = game_layer::find_links(db, games.iter().map(|game| game.id)).await?;
+        for (game, stacks) in games.into_iter().zip(stacks) {
+            let links: Vec<_> = links
+                .iter()
+                .filter(|(link, _)| link.gameid == game.id)
+                .cloned()
+                .collect();
+            let rank = game::grade_rank(&game.grading_with(&stacks, &links));
+            if game.grade_rank == Some(rank) {
+                continue;
+            }
+            let mut active = game.into_active_model();
+            active.grade_rank = ActiveValue::Set(Some(rank));
+            active.update(db).await?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::build;
+    use crate::entity::game;
+    use crate::entity::game_layer;
+    use crate::entity::game_stack;
+    use crate::entity::layer;
+    use crate::entity::report;
+    use crate::entity::report_layer;
+    use crate::entity::tester;
+    use crate::fixtures;
+    use enumflags2::make_bitflags;
+    use enumflags2::BitFlags;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::EntityTrait;
+    use sea_orm::IntoActiveModel;
+    use sea_orm::ModelTrait;
+    use sea_orm::QueryOrder;
+
+    use crate::entity::game::Compatibility;
+    use crate::entity::game::CompatibilityLayerItem;
+    use crate::entity::game::SupportLevel;
+
+    use super::recompute;
+    use super::recompute_all;
+    use super::regrade;
+    use super::regrade_all;
+    use super::version_key;
+    use super::AggregationRules;
+
+    #[test]
+    fn aggregation_consensus() {
+        let rules = AggregationRules::default();
+        assert_eq!(rules.consensus(&[]), None);
+        let (level, confidence) = rules
+            .consensus(&[
+                (SupportLevel::PERFECT, 1.0),
+                (SupportLevel::GOOD, 1.0),
+                (SupportLevel::GOOD, 1.0),
+                (SupportLevel::FAIL, 1.0),
+            ])
+            .unwrap();
+        assert_eq!(level, SupportLevel::GOOD);
+        assert!((confidence - 0.5).abs() < 1e-9);
+        // 总权重不足时置信度降低
+        let (level, confidence) = rules.consensus(&[(SupportLevel::GREAT, 1.0)]).unwrap();
+        assert_eq!(level, SupportLevel::GREAT);
+        assert!((confidence - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn aggregation_version_key() {
+        assert!(version_key("0.3.10") > version_key("0.3.2"));
+        assert!(version_key("wine-9.0") > version_key("8.21"));
+        assert!(version_key("") < version_key("1"));
+    }
+
+    #[tokio::test]
+    async fn aggregation_recompute() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = game::ActiveModel {
+            supportlevel: ActiveValue::Set(SupportLevel::FAIL),
+            ..fixtures::game(1)
+        };
+        game.insert(&db).await.unwrap();
+        recompute(&db, 1).await.unwrap();
+        let game = game::Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+        assert_eq!(game.supportlevel, SupportLevel::FAIL);
+        assert_eq!(game.confidence, None);
+        assert_eq!(game.best_supportlevel, None);
+
+        let trusted = tester::ActiveModel {
+            name: ActiveValue::Set("alice".to_string()),
+            reputation: ActiveValue::Set(3.0),
+        };
+        trusted.insert(&db).await.unwrap();
+        let mut layers = Vec::new();
+        for version in ["0.3.2", "0.3.10"] {
+            let layer = layer::ActiveModel {
+                name: ActiveValue::Set("box64".to_string()),
+                kind: ActiveValue::Set(layer::LayerKind::TRANSLATOR),
+                version: ActiveValue::Set(Some(version.to_string())),
+                ..Default::default()
+            };
+            layers.push(layer.insert(&db).await.unwrap());
+        }
+        // 两份旧版兼容层上的报告不敌一份高信誉测试者在新版上的报告
+        for (tester, supportlevel, layer) in [
+            ("bob", SupportLevel::BAD, &layers[0]),
+            ("carol", SupportLevel::BAD, &layers[0]),
+            ("alice", SupportLevel::GREAT, &layers[1]),
+        ] {
+            let report = report::ActiveModel {
+                gameid: ActiveValue::Set(1),
+                tester: ActiveValue::Set(tester.to_string()),
+                supportlevel: ActiveValue::Set(supportlevel),
+                compat: ActiveValue::Set(Compatibility(BitFlags::default())),
+                created_at: ActiveValue::Set(chrono::Utc::now()),
+                ..Default::default()
+            };
+            let report = report.insert(&db).await.unwrap();
+            let link = report_layer::ActiveModel {
+                reportid: ActiveValue::Set(report.id),
+                layerid: ActiveValue::Set(layer.id),
+            };
+            link.insert(&db).await.unwrap();
+        }
+        recompute(&db, 1).await.unwrap();
+        let game = game::Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+        assert_eq!(game.supportlevel, SupportLevel::GREAT);
+        assert!((game.confidence.unwrap() - 0.75).abs() < 1e-9);
+        assert_eq!(game.best_supportlevel, Some(SupportLevel::GREAT));
+        assert!(game.best_confidence.unwrap() > 0.9);
+        // 不同版本的兼容层各自成为一个组合，旧版上的报告权重打了折扣
+        let stacks = game
+            .find_related(game_stack::Entity)
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(stacks.len(), 2);
+        let outdated = stacks
+            .iter()
+            .find(|stack| stack.layers == layers[0].id.to_string())
+            .unwrap();
+        assert_eq!(outdated.supportlevel, SupportLevel::BAD);
+        assert!(outdated.confidence < 0.5);
+    }
+
+    #[tokio::test]
+    async fn aggregation_stacks() {
+        let (db, _guard) = fixtures::setup().await;
+        let game = fixtures::game(1);
+        game.insert(&db).await.unwrap();
+        let latx = Compatibility(make_bitflags!(CompatibilityLayerItem::{LATX}));
+        let box64 = Compatibility(make_bitflags!(CompatibilityLayerItem::{WINE | BOX64}));
+        for (supportlevel, compat) in [
+            (SupportLevel::FAIL, latx),
+            (SupportLevel::FAIL, latx),
+            (SupportLevel::PERFECT, box64),
+        ] {
+            let report = report::ActiveModel {
+                gameid: ActiveValue::Set(1),
+                tester: ActiveValue::Set("bob".to_string()),
+                supportlevel: ActiveValue::Set(supportlevel),
+                compat: ActiveValue::Set(compat),
+                created_at: ActiveValue::Set(chrono::Utc::now()),
+                ..Default::default()
+            };
+            report.insert(&db).await.unwrap();
+        }
+        recompute(&db, 1).await.unwrap();
+
+        let game = game::Entity::find_by_id(1).one(&db).await.unwrap().unwrap();
+        let stacks = game
+            .find_related(game_stack::Entity)
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(stacks.len(), 2);
+        let latx_stack = stacks.iter().find(|stack| stack.compat == latx).unwrap();
+        assert_eq!(latx_stack.supportlevel, SupportLevel::FAIL);
+        // 游戏的运行效果为全部报告的汇总结果，评级最好的组合另外保存；用户填写的兼容层不变
+        assert_eq!(game.supportlevel, SupportLevel::FAIL);
+        assert_eq!(game.best_supportlevel, Some(SupportLevel::PERFECT));
+        assert_eq!(game.compat, Compatibility::default());
+        // 没有登记为必需的兼容层，组合中用到的兼容层不影响评级
+        assert_eq!(game.grading_with(&stacks, &[]), "SSS");
+        // 重新汇总全部游戏得到同样的结果
+        assert_eq!(recompute_all(&db).await.unwrap(), 1);
+        let again = game
+            .find_related(game_stack::Entity)
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(again, stacks);
+    }
+
+    #[tokio::test]
+    async fn aggregation_regrade() {
+        let (db, _guard) = fixtures::setup().await;
+        for (id, supportlevel) in [
+            (1, SupportLevel::BAD),
+            (2, SupportLevel::PERFECT),
+            (3, SupportLevel::GOOD),
+        ] {
+            let game = game::ActiveModel {
+                supportlevel: ActiveValue::Set(supportlevel),
+                ..fixtures::game(id)
+            };
+            game.insert(&db).await.unwrap();
+        }
+        assert_eq!(regrade_all(&db, true).await.unwrap(), 3);
+        assert_eq!(regrade_all(&db, true).await.unwrap(), 0);
+        let ids: Vec<i32> = game::Entity::find()
+            .order_by_asc(game::Column::GradeRank)
+            .all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|game| game.id)
+            .collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+
+        // 必需的兼容层使评级的重复次数减少
+        let mut game = game::Entity::find_by_id(2)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into_active_model();
+        game.compat = ActiveValue::Set(Compatibility(
+            make_bitflags!(CompatibilityLayerItem::{WINE | BOX64}),
+        ));
+        let game = game.update(&db).await.unwrap();
+        let before = game.grade_rank.unwrap();
+        regrade(&db, 2).await.unwrap();
+        let game = game::Entity::find_by_id(2).one(&db).await.unwrap().unwrap();
+        assert_eq!(game.grade_rank.unwrap(), before);
+        for (name, kind) in [
+            ("wine", layer::LayerKind::WINE),
+            ("box64", layer::LayerKind::TRANSLATOR),
+        ] {
+            let layer = layer::ActiveModel {
+                name: ActiveValue::Set(name.to_string()),
+                kind: ActiveValue::Set(kind),
+                ..Default::default()
+            };
+            let layer = layer.insert(&db).await.unwrap();
+            let link = game_layer::ActiveModel {
+                gameid: ActiveValue::Set(2),
+                layerid: ActiveValue::Set(layer.id),
+                role: ActiveValue::Set(game_layer::LayerRole::REQUIRED),
+            };
+            link.insert(&db).await.unwrap();
+        }
+        regrade(&db, 2).await.unwrap();
+        let game = game::Entity::find_by_id(2).one(&db).await.unwrap().unwrap();
+        assert!(game.grade_rank.unwrap() > before);
+    }
+
+    #[tokio::test]
+    async fn aggregation_builds() {
+        let (db, _guard) = fixtures::setup().await;
+        let game = fixtures::game(1);
+        game.insert(&db).await.unwrap();
+        let mut builds = Vec::new();
+        for version in ["1.0", "1.1", "1.2"] {
+            let build = build::ActiveModel {
+                gameid: ActiveValue::Set(1),
+                version: ActiveValue::Set(version.to_string()),
+                ..Default::default()
+            };
+            builds.push(build.insert(&db).await.unwrap());
+        }
+        // 1.1 的补丁导致游戏无法运行，1.2 未经测试
+        for (supportlevel, buildid) in [
+            (SupportLevel::GREAT, Some(builds[0].id)),
+            (SupportLevel::FAIL, Some(builds[1].id)),
+            (SupportLevel::GREAT, None),
+        ] {
+            let report = report::ActiveModel {
+                gameid: ActiveValue::Set(1),
+                tester: ActiveValue::Set("bob".to_string()),
+                supportlevel: ActiveValue::Set(supportlevel),
+                compat: ActiveValue::Set(Compatibility(BitFlags::default())),
+                buildid: ActiveValue::Set(buildid),
+                created_at: ActiveValue::Set(chrono::Utc::now()),
+                ..Default::default()
+            };
+            report.insert(&db).await.unwrap();
+        }
+        recompute(&db, 1).await.unwrap();
+
+        let builds = build::Entity::find().all(&db).await.unwrap();
+        assert_eq!(builds[0].supportlevel, Some(SupportLevel::GREAT));
+        assert!(builds[0].known_good());
+        assert_eq!(builds[1].supportlevel, Some(SupportLevel::FAIL));
+        assert!(!builds[1].known_good());
+        assert_eq!(builds[2].supportlevel, None);
+        assert_eq!(builds[2].confidence, None);
+    }
+}
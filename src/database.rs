@@ -0,0 +1,214 @@
+use config::{Config, ConfigError};
+use log::warn;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// 数据库连接池的设置，对应设置项 `[DATABASE_POOL]`，未给出的项使用默认值。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// 建立新连接的超时时间。
+    pub connect_timeout_secs: u64,
+    /// 连接池已满时等待空闲连接的超时时间。
+    pub acquire_timeout_secs: u64,
+    /// 空闲连接在被关闭前保留的时间。
+    pub idle_timeout_secs: u64,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        PoolSettings {
+            max_connections: 16,
+            min_connections: 1,
+            connect_timeout_secs: 8,
+            acquire_timeout_secs: 8,
+            idle_timeout_secs: 600,
+        }
+    }
+}
+
+impl PoolSettings {
+    pub fn options(&self, url: &str) -> ConnectOptions {
+        let mut options = ConnectOptions::new(url);
+        options
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .acquire_timeout(Duration::from_secs(self.acquire_timeout_secs))
+            .idle_timeout(Duration::from_secs(self.idle_timeout_secs));
+        options
+    }
+}
+
+/// 从设置中读取连接池设置。没有 `[DATABASE_POOL]` 时使用默认值，格式错误时返回错误。
+fn load(settings: &Config) -> Result<PoolSettings, ConfigError> {
+    match settings.get::<PoolSettings>("DATABASE_POOL") {
+        Err(ConfigError::NotFound(_)) => {
+            warn!("No database pool settings, using defaults");
+            Ok(PoolSettings::default())
+        }
+        result => result,
+    }
+}
+
+/// 建立连接池，启动时调用一次，之后由各请求共享。
+pub async fn connect(url: &str) -> Result<DatabaseConnection, DbErr> {
+    let pool = load(&crate::settings)
+        .map_err(|e| DbErr::Custom(format!("Invalid database pool settings: {}", e)))?;
+    Database::connect(pool.options(url)).await
+}
+
+/// 测试结束时删除 [`connect_test`] 新建的数据库。
+#[cfg(test)]
+pub struct TestDatabase {
+    server: Option<(String, String)>,
+}
+
+#[cfg(test)]
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        use sea_orm::{ConnectionTrait, DatabaseBackend};
+
+        let Some((url, name)) = self.server.take() else {
+            return;
+        };
+        // Drop 中不能等待异步任务，在单独的线程中执行
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let server = Database::connect(&url).await.unwrap();
+                let statement = match server.get_database_backend() {
+                    // 测试中的连接池可能尚未关闭
+                    DatabaseBackend::Postgres => {
+                        format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", name)
+                    }
+                    _ => format!("DROP DATABASE IF EXISTS {}", name),
+                };
+                server.execute_unprepared(&statement).await.unwrap();
+            });
+        })
+        .join()
+        .unwrap();
+    }
+}
+
+/// 为测试建立一个空数据库，后端与编译时启用的特性一致。
+///
+/// 设置了 `TEST_DATABASE_URL` 时，在该服务器上为每次调用新建一个数据库，
+/// 以免并行的测试互相影响，返回的 [`TestDatabase`] 离开作用域时将其删除；
+/// 否则使用 SQLite 内存数据库。
+#[cfg(test)]
+pub async fn connect_test() -> (DatabaseConnection, TestDatabase) {
+    use sea_orm::ConnectionTrait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let url = match std::env::var("TEST_DATABASE_URL") {
+        Ok(url) => url,
+        Err(_) if cfg!(feature = "sqlite") => {
+            let db = Database::connect("sqlite::memory:").await.unwrap();
+            return (db, TestDatabase { server: None });
+        }
+        Err(_) => panic!("TEST_DATABASE_URL is required when the sqlite feature is disabled"),
+    };
+    let name = format!(
+        "loonggamedb_test_{}_{}_{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_micros(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let server = Database::connect(&url).await.unwrap();
+    server
+        .execute_unprepared(&format!("CREATE DATABASE {}", name))
+        .await
+        .unwrap();
+    let guard = TestDatabase {
+        server: Some((url.clone(), name.clone())),
+    };
+    let db = Database::connect(test_url(&url, &name)).await.unwrap();
+    (db, guard)
+}
+
+/// 将连接串中的数据库名替换为 `name`，保留 `?sslmode=` 等参数。
+#[cfg(test)]
+fn test_url(url: &str, name: &str) -> String {
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (url, None),
+    };
+    let (base, _) = path.rsplit_once('/').unwrap();
+    match query {
+        Some(query) => format!("{}/{}?{}", base, name, query),
+        None => format!("{}/{}", base, name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use config::{Config, File, FileFormat};
+
+    use super::load;
+    use super::test_url;
+    use super::PoolSettings;
+
+    fn settings(source: &str) -> Config {
+        Config::builder()
+            .add_source(File::from_str(source, FileFormat::Toml))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn database_settings_match_default() {
+        let settings = Config::builder()
+            .add_source(File::with_name("settings.toml"))
+            .build()
+            .unwrap();
+        assert_eq!(load(&settings).unwrap(), PoolSettings::default());
+    }
+
+    #[test]
+    fn database_settings_load() {
+        assert_eq!(load(&settings("")).unwrap(), PoolSettings::default());
+        let pool = load(&settings("[DATABASE_POOL]\nMAX_CONNECTIONS = 4")).unwrap();
+        assert_eq!(pool.max_connections, 4);
+        assert_eq!(pool.idle_timeout_secs, 600);
+        assert!(load(&settings("[DATABASE_POOL]\nMAX_CONNECTIONS = \"many\"")).is_err());
+        assert!(load(&settings("DATABASE_POOL = 16")).is_err());
+    }
+
+    #[tokio::test]
+    async fn database_pool_connect() {
+        let pool = PoolSettings {
+            max_connections: 1,
+            ..Default::default()
+        };
+        let url = std::env::var("TEST_DATABASE_URL").unwrap_or("sqlite::memory:".to_string());
+        let db = sea_orm::Database::connect(pool.options(&url))
+            .await
+            .unwrap();
+        db.ping().await.unwrap();
+    }
+
+    #[test]
+    fn database_test_url() {
+        assert_eq!(
+            test_url(
+                "postgres://u:p@localhost:5432/postgres?sslmode=require",
+                "t1"
+            ),
+            "postgres://u:p@localhost:5432/t1?sslmode=require"
+        );
+        assert_eq!(
+            test_url("mysql://u@localhost/mysql", "t1"),
+            "mysql://u@localhost/t1"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 游戏的别名，包括其他语言的译名与常用缩写。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "aliases")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub gameid: i32,
+    pub name: String,
+    /// 语言代码，如 "zh"、"en"、"ja"；缩写等不属于特定语言的别名为空。
+    pub language: Option<String>,
+    /// 是否为该语言下的主要名称。
+    pub is_primary: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::alias;
+    use crate::entity::game;
+    use crate::fixtures;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::ModelTrait;
+
+    #[tokio::test]
+    async fn aliases_write_db() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = game::ActiveModel {
+            name: ActiveValue::Set("Genshin Impact".to_string()),
+            ..fixtures::game(1)
+        };
+        let game = game.insert(&db).await.unwrap();
+        for (name, language, is_primary) in [
+            ("原神", Some("zh"), true),
+            ("原神", Some("ja"), true),
+            ("GI", None, false),
+        ] {
+            let alias = alias::ActiveModel {
+                gameid: ActiveValue::Set(game.id),
+                name: ActiveValue::Set(name.to_string()),
+                language: ActiveValue::Set(language.map(|l| l.to_string())),
+                is_primary: ActiveValue::Set(is_primary),
+                ..Default::default()
+            };
+            alias.insert(&db).await.unwrap();
+        }
+
+        let aliases = game.find_related(alias::Entity).all(&db).await.unwrap();
+        assert_eq!(aliases.len(), 3);
+        assert_eq!(aliases[1].language.as_deref(), Some("ja"));
+        assert!(!aliases[2].is_primary);
+    }
+}
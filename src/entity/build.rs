@@ -0,0 +1,98 @@
+use super::game::SupportLevel;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 游戏的某一版本或发行版本，如 Steam 版 1.2.3。补丁常常改变游戏在 LoongArch 上的表现，
+/// 报告可以指明测试的是哪个版本。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "builds")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub gameid: i32,
+    pub version: String,
+    /// 发行渠道或版本名，如 "Steam"、"GOG"、"年度版"。
+    pub edition: Option<String>,
+    pub released_on: Option<Date>,
+    /// 主程序的 SHA-256，小写十六进制。
+    pub exe_sha256: Option<String>,
+    /// 由该版本的报告汇总得出，没有报告时为空。
+    pub supportlevel: Option<SupportLevel>,
+    pub confidence: Option<f64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+    #[sea_orm(has_many = "super::report::Entity")]
+    Report,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl Related<super::report::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Report.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 报告汇总的运行效果不低于 GOOD 时视为可用。
+    pub fn known_good(&self) -> bool {
+        self.supportlevel
+            .as_ref()
+            .is_some_and(|level| level.clone() as u8 <= SupportLevel::GOOD as u8)
+    }
+}
+
+/// 检查是否为 64 位十六进制的 SHA-256。
+pub fn valid_sha256(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::game::SupportLevel;
+
+    use super::valid_sha256;
+    use super::Model;
+
+    #[test]
+    fn builds_validate() {
+        assert!(valid_sha256(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        ));
+        assert!(!valid_sha256("e3b0c442"));
+        assert!(!valid_sha256(
+            "z3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        ));
+
+        let mut build = Model {
+            id: 1,
+            gameid: 1,
+            version: "1.2.3".to_string(),
+            edition: Some("Steam".to_string()),
+            released_on: chrono::NaiveDate::from_ymd_opt(2024, 5, 1),
+            exe_sha256: None,
+            supportlevel: None,
+            confidence: None,
+        };
+        assert!(!build.known_good());
+        build.supportlevel = Some(SupportLevel::GOOD);
+        assert!(build.known_good());
+        build.supportlevel = Some(SupportLevel::BAD);
+        assert!(!build.known_good());
+    }
+}
@@ -0,0 +1,109 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 运行库的种类。
+#[derive(PartialEq, Eq, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum DependencyKind {
+    /// 图形 API 转换，如 DXVK、VKD3D-Proton
+    GRAPHICS = 0,
+    /// .NET Framework
+    DOTNET = 1,
+    /// Visual C++ 运行库
+    VCREDIST = 2,
+    /// Mono 或 XNA 等托管运行环境
+    MONO = 3,
+    /// DirectX 可再发行组件，如 d3dx9、xact
+    DIRECTX = 4,
+    /// 字体
+    FONT = 5,
+    OTHER = 6,
+}
+
+/// 需要安装到 wine 前缀中的运行库，如 DXVK、.NET、vcredist。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "dependencies")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub name: String,
+    pub kind: DependencyKind,
+    /// 可以用 winetricks 安装时对应的动词，如 "dotnet48"。
+    pub winetricks: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::game_dependency::Relation::Game.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::game_dependency::Relation::Dependency.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::dependency;
+    use crate::entity::game;
+    use crate::entity::game_dependency;
+    use crate::fixtures;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::ColumnTrait;
+    use sea_orm::EntityTrait;
+    use sea_orm::QueryFilter;
+
+    use crate::entity::game::SupportLevel;
+
+    use super::DependencyKind;
+
+    #[tokio::test]
+    async fn dependencies_link_games() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = game::ActiveModel {
+            supportlevel: ActiveValue::Set(SupportLevel::GREAT),
+            ..fixtures::game(1)
+        };
+        let game = game.insert(&db).await.unwrap();
+        for (name, kind, version) in [
+            ("DXVK", DependencyKind::GRAPHICS, Some(">=2.0")),
+            (".NET Framework", DependencyKind::DOTNET, None),
+        ] {
+            let dependency = dependency::ActiveModel {
+                name: ActiveValue::Set(name.to_string()),
+                kind: ActiveValue::Set(kind),
+                ..Default::default()
+            };
+            let dependency = dependency.insert(&db).await.unwrap();
+            let link = game_dependency::ActiveModel {
+                gameid: ActiveValue::Set(game.id),
+                dependencyid: ActiveValue::Set(dependency.id),
+                version: ActiveValue::Set(version.map(str::to_string)),
+            };
+            link.insert(&db).await.unwrap();
+        }
+
+        let links = game_dependency::Entity::find()
+            .inner_join(dependency::Entity)
+            .filter(dependency::Column::Name.eq("DXVK"))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].version.as_deref(), Some(">=2.0"));
+        let games = game::Entity::find()
+            .inner_join(dependency::Entity)
+            .filter(dependency::Column::Kind.eq(DependencyKind::DOTNET))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(games.len(), 1);
+    }
+}
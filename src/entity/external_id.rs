@@ -0,0 +1,101 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 游戏的发行平台。
+#[derive(PartialEq, Eq, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "lowercase")]
+pub enum Store {
+    #[sea_orm(string_value = "steam")]
+    STEAM,
+    #[sea_orm(string_value = "gog")]
+    GOG,
+    #[sea_orm(string_value = "epic")]
+    EPIC,
+    #[sea_orm(string_value = "itch")]
+    ITCH,
+}
+
+/// 游戏在各发行平台上的标识，如 Steam AppID。同一平台的标识只能对应一个游戏。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "external_ids")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub store: Store,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub external_id: String,
+    pub gameid: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::external_id;
+    use crate::entity::game;
+    use crate::fixtures;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::EntityTrait;
+
+    use crate::entity::game::SupportLevel;
+
+    use super::Store;
+
+    #[tokio::test]
+    async fn external_ids_unique() {
+        let (db, _guard) = fixtures::setup().await;
+
+        for id in [1, 2] {
+            let game = game::ActiveModel {
+                supportlevel: ActiveValue::Set(SupportLevel::GREAT),
+                ..fixtures::game(id)
+            };
+            game.insert(&db).await.unwrap();
+        }
+        let steam = external_id::ActiveModel {
+            store: ActiveValue::Set(Store::STEAM),
+            external_id: ActiveValue::Set("620".to_string()),
+            gameid: ActiveValue::Set(1),
+        };
+        steam.insert(&db).await.unwrap();
+        // 不同平台可以使用相同的标识
+        let gog = external_id::ActiveModel {
+            store: ActiveValue::Set(Store::GOG),
+            external_id: ActiveValue::Set("620".to_string()),
+            gameid: ActiveValue::Set(2),
+        };
+        gog.insert(&db).await.unwrap();
+        let duplicate = external_id::ActiveModel {
+            store: ActiveValue::Set(Store::STEAM),
+            external_id: ActiveValue::Set("620".to_string()),
+            gameid: ActiveValue::Set(2),
+        };
+        assert!(duplicate.insert(&db).await.is_err());
+
+        let (_, game) = external_id::Entity::find_by_id((Store::STEAM, "620".to_string()))
+            .find_also_related(game::Entity)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(game.unwrap().name, "Test 1");
+    }
+}
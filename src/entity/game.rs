@@ -1,9 +1,10 @@
 use enumflags2::{bitflags, BitFlags};
 use sea_orm::entity::prelude::*;
-use sea_orm::sea_query::Value;
+use sea_orm::sea_query::{ExprTrait, SimpleExpr, Value};
+use sea_orm::JsonValue;
 use serde::{Deserialize, Serialize};
-#[derive(PartialEq, Eq, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
-#[sea_orm(rs_type = "u8", db_type = "Integer")]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum SupportLevel {
     PERFECT = 0,
     GREAT = 1,
@@ -12,10 +13,35 @@ pub enum SupportLevel {
     FAIL = 4,
 }
 
+/// LoongArch 的 ABI：旧世界、新世界，以及新世界上借助 liblol 运行旧世界程序。
+#[derive(PartialEq, Eq, Hash, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum Abi {
+    OLDWORLD = 0,
+    NEWWORLD = 1,
+    LIBLOL = 2,
+}
+
+/// 游戏与其上级游戏的关系。
+#[derive(PartialEq, Eq, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum ParentKind {
+    /// 需要本体才能运行的追加内容
+    DLC = 0,
+    /// 资料片，可能可以独立运行
+    EXPANSION = 1,
+    /// 重制版或复刻版
+    REMASTER = 2,
+    /// 移植到其他平台的版本
+    PORT = 3,
+}
+
 #[bitflags]
 #[repr(u32)]
-#[derive(PartialEq, Eq, DeriveActiveEnum, EnumIter, Debug, Clone, Copy, Deserialize, Serialize)]
-#[sea_orm(rs_type = "u32", db_type = "Integer")]
+#[derive(
+    PartialEq, Eq, Hash, DeriveActiveEnum, EnumIter, Debug, Clone, Copy, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum CompatibilityLayerItem {
     WINE = 1,
     LATX = 2,
@@ -23,12 +49,22 @@ pub enum CompatibilityLayerItem {
     BOX64 = 8,
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Debug, Clone, Copy, Default)]
 pub struct Compatibility(pub BitFlags<CompatibilityLayerItem>);
 
+impl Compatibility {
+    /// 数据库中以有符号整数保存，PostgreSQL 没有无符号类型。
+    fn from_column(value: i32) -> Result<Self, String> {
+        let bits = u32::try_from(value).map_err(|e| e.to_string())?;
+        let layer: BitFlags<CompatibilityLayerItem> =
+            BitFlags::from_bits(bits).map_err(|e| e.to_string())?;
+        Ok(Compatibility(layer))
+    }
+}
+
 impl std::convert::From<Compatibility> for Value {
     fn from(source: Compatibility) -> Self {
-        source.0.bits().into()
+        Value::Int(Some(source.0.bits() as i32))
     }
 }
 
@@ -38,9 +74,9 @@ impl<'de> Deserialize<'de> for Compatibility {
         D: serde::Deserializer<'de>,
     {
         let value: u32 = serde::Deserialize::deserialize(deserializer)?;
-        let layer: BitFlags<CompatibilityLayerItem> = BitFlags::from_bits(value).unwrap();
-        let result = Compatibility { 0: layer };
-        Ok(result)
+        let layer: BitFlags<CompatibilityLayerItem> =
+            BitFlags::from_bits(value).map_err(serde::de::Error::custom)?;
+        Ok(Compatibility(layer))
     }
 }
 
@@ -58,28 +94,31 @@ impl sea_orm::TryGetable for Compatibility {
         res: &QueryResult,
         index: I,
     ) -> Result<Self, sea_orm::TryGetError> {
-        let value = <u32 as sea_orm::TryGetable>::try_get_by(res, index).unwrap();
-        let layer: BitFlags<CompatibilityLayerItem> = BitFlags::from_bits(value).unwrap();
-        let result = Compatibility { 0: layer };
-        Ok(result)
+        let value = <i32 as sea_orm::TryGetable>::try_get_by(res, index)?;
+        Compatibility::from_column(value).map_err(|e| sea_orm::TryGetError::DbErr(DbErr::Type(e)))
+    }
+}
+
+impl sea_orm::TryFromU64 for Compatibility {
+    fn try_from_u64(n: u64) -> Result<Self, DbErr> {
+        let value = i32::try_from(n).map_err(|e| DbErr::Custom(e.to_string()))?;
+        Compatibility::from_column(value).map_err(DbErr::Type)
     }
 }
 
 impl sea_orm::sea_query::ValueType for Compatibility {
     fn try_from(v: Value) -> Result<Self, sea_orm::sea_query::ValueTypeErr> {
-        let value = <u32 as sea_orm::sea_query::ValueType>::try_from(v).unwrap();
-        let layer: BitFlags<CompatibilityLayerItem> = BitFlags::from_bits(value).unwrap();
-        let result = Compatibility { 0: layer };
-        Ok(result)
+        let value = <i32 as sea_orm::sea_query::ValueType>::try_from(v)?;
+        Compatibility::from_column(value).map_err(|_| sea_orm::sea_query::ValueTypeErr)
     }
     fn type_name() -> String {
         stringify!(Compatibility).to_owned()
     }
     fn array_type() -> sea_orm::sea_query::ArrayType {
-        sea_orm::sea_query::ArrayType::Unsigned
+        sea_orm::sea_query::ArrayType::Int
     }
     fn column_type() -> ColumnType {
-        sea_orm::sea_query::ColumnType::Unsigned
+        sea_orm::sea_query::ColumnType::Integer
     }
 }
 
@@ -88,59 +127,271 @@ impl sea_orm::sea_query::ValueType for Compatibility {
 pub struct Model {
     pub name: String,
     #[sea_orm(primary_key)]
-    pub id: u32,
+    pub id: i32,
     pub supportlevel: SupportLevel,
     pub compat: Compatibility,
+    /// 由全部报告汇总得出 `supportlevel` 时的置信度，范围为 0 到 1；尚无报告时为空。
+    pub confidence: Option<f64>,
+    pub developer: Option<String>,
+    pub publisher: Option<String>,
+    pub release_year: Option<i16>,
+    /// 上级游戏，如 DLC 所属的本体，与 `parent_kind` 同时为空或同时非空。
+    pub parent_id: Option<i32>,
+    pub parent_kind: Option<ParentKind>,
+    /// 软删除的时间，非空时该游戏对 `/info` 与 `/search` 不可见。
+    pub deleted_at: Option<DateTimeUtc>,
+    /// 评级的排序键，见 [`grade_rank`]。由 [`crate::aggregation::regrade`] 维护，供按评级排序时在数据库中分页。
+    pub grade_rank: Option<i32>,
+    /// 评级最好的兼容层组合的运行效果，由 [`crate::aggregation::recompute`] 维护；尚无报告时为空。
+    pub best_supportlevel: Option<SupportLevel>,
+    /// 评级最好的兼容层组合的置信度。
+    pub best_confidence: Option<f64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::report::Entity")]
+    Report,
+    #[sea_orm(has_many = "super::alias::Entity")]
+    Alias,
+    #[sea_orm(has_many = "super::external_id::Entity")]
+    ExternalId,
+    #[sea_orm(has_many = "super::game_stack::Entity")]
+    GameStack,
+    #[sea_orm(has_many = "super::build::Entity")]
+    Build,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_delete = "SetNull"
+    )]
+    Parent,
+}
+
+/// 从游戏到其上级游戏。
+pub struct ParentLink;
+
+impl Linked for ParentLink {
+    type FromEntity = Entity;
+    type ToEntity = Entity;
+
+    fn link(&self) -> Vec<RelationDef> {
+        vec![Relation::Parent.def()]
+    }
+}
+
+/// 从游戏到其 DLC、资料片等下级游戏。
+pub struct ChildrenLink;
+
+impl Linked for ChildrenLink {
+    type FromEntity = Entity;
+    type ToEntity = Entity;
+
+    fn link(&self) -> Vec<RelationDef> {
+        vec![Relation::Parent.def().rev()]
+    }
+}
+
+impl Related<super::report::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Report.def()
+    }
+}
+
+impl Related<super::alias::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Alias.def()
+    }
+}
+
+impl Related<super::external_id::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ExternalId.def()
+    }
+}
+
+impl Related<super::game_stack::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GameStack.def()
+    }
+}
+
+impl Related<super::build::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Build.def()
+    }
+}
+
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::game_tag::Relation::Tag.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::game_tag::Relation::Game.def().rev())
+    }
+}
+
+impl Related<super::dependency::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::game_dependency::Relation::Dependency.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::game_dependency::Relation::Game.def().rev())
+    }
+}
+
+impl Related<super::layer::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::game_layer::Relation::Layer.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::game_layer::Relation::Game.def().rev())
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Entity {
+    /// 查询未被软删除的游戏。
+    pub fn find_visible() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+}
+
+impl Compatibility {
+    /// 游戏具备其中全部兼容层的条件。
+    pub fn contained_by_column(self) -> SimpleExpr {
+        let bits = self.0.bits() as i32;
+        Expr::col((Entity, Column::Compat)).bit_and(bits).eq(bits)
+    }
+
+    /// 游戏不具备其中任何兼容层的条件。
+    pub fn excluded_by_column(self) -> SimpleExpr {
+        Expr::col((Entity, Column::Compat))
+            .bit_and(self.0.bits() as i32)
+            .eq(0)
+    }
+}
+
 impl Model {
-    fn grading(self) -> String {
-        let grade: &str;
-        match self.supportlevel {
-            SupportLevel::PERFECT => grade = "S",
-            SupportLevel::GREAT => grade = "A",
-            SupportLevel::GOOD => grade = "B",
-            SupportLevel::BAD => grade = "C",
-            SupportLevel::FAIL => grade = "D",
+    /// 将部分字段的 JSON 应用到游戏上，只有 JSON 中出现的字段会被标记为已修改。
+    /// `id`、`deleted_at` 以及由报告汇总得出的 `confidence`、`grade_rank`、`best_*` 不能通过此方式修改，
+    /// 与原值不同时返回错误。`supportlevel` 在游戏有报告后同样由汇总得出，由调用方检查。
+    pub fn patch(self, patch: &JsonValue) -> Result<ActiveModel, DbErr> {
+        super::patch::apply(
+            self,
+            patch,
+            &[
+                Column::Id,
+                Column::Confidence,
+                Column::DeletedAt,
+                Column::GradeRank,
+                Column::BestSupportlevel,
+                Column::BestConfidence,
+            ],
+        )
+    }
+
+    /// 检查上级游戏是否合法，不合法时返回原因。上级游戏必须存在且未被删除，
+    /// 且只允许一层：上级游戏自身不能有上级，已有下级的游戏也不能再挂到别的游戏下。
+    pub async fn check_parent<C: ConnectionTrait>(
+        &self,
+        db: &C,
+    ) -> Result<Option<&'static str>, DbErr> {
+        let Some(parent_id) = self.parent_id else {
+            if self.parent_kind.is_some() {
+                return Ok(Some("Parent kind requires a parent game."));
+            }
+            return Ok(None);
+        };
+        if self.parent_kind.is_none() {
+            return Ok(Some("Parent game requires a parent kind."));
+        }
+        if parent_id == self.id {
+            return Ok(Some("A game cannot be its own parent."));
+        }
+        let parent = Entity::find_visible()
+            .filter(Column::Id.eq(parent_id))
+            .one(db)
+            .await?;
+        match parent {
+            None => return Ok(Some("Parent game not found.")),
+            Some(parent) if parent.parent_id.is_some() => {
+                return Ok(Some("Parent game must not have a parent itself."))
+            }
+            Some(_) => {}
         }
-        let mut grade = grade.to_string();
-        if self.compat.0.is_empty() {
-            // 不需要任何兼容层
-            grade = grade.repeat(3);
-        } else if self.compat.0 == CompatibilityLayerItem::WINE {
-            // 只需要wine，但是这种程序应该不存在
-            grade = grade.repeat(2)
-        } else if !(self.compat.0.contains(CompatibilityLayerItem::WINE))
-            && !(self.compat.0.is_empty())
-        {
-            // 只需要转译工具
-            grade = grade.repeat(2)
+        let children = Entity::find()
+            .filter(Column::ParentId.eq(self.id))
+            .count(db)
+            .await?;
+        if children > 0 {
+            return Ok(Some("A game with children cannot have a parent."));
         }
-        grade
+        Ok(None)
     }
+
+    /// 按当前生效的评级规则计算评级，见 [`crate::grading`]。
+    pub fn grading(&self) -> String {
+        crate::grading::rules().grade(&self.supportlevel, &self.compat, None)
+    }
+
+    /// 取各兼容层组合中最好的评级；尚无组合记录时按游戏自身的字段计算。
+    /// 两种情况下都只计 `links` 中登记为必需的兼容层。
+    pub fn grading_with(
+        &self,
+        stacks: &[super::game_stack::Model],
+        links: &[super::game_layer::LayerLink],
+    ) -> String {
+        stacks
+            .iter()
+            .map(|stack| stack.grading(links))
+            .min_by_key(|grade| grade_order(grade))
+            .unwrap_or_else(|| {
+                let compat = self.compat.required(links);
+                crate::grading::rules().grade(&self.supportlevel, &compat, None)
+            })
+    }
+}
+
+/// 评级的排序键，按从好到差排序；无法识别的评级排在最后。
+pub fn grade_order(grade: &str) -> (usize, std::cmp::Reverse<usize>) {
+    crate::grading::rules().order(grade)
+}
+
+/// 可写入数据库的评级排序键，越小越好，顺序与 [`grade_order`] 一致。
+pub fn grade_rank(grade: &str) -> i32 {
+    let (letter, std::cmp::Reverse(repeat)) = grade_order(grade);
+    letter as i32 * 1000 - repeat.min(999) as i32
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::fixtures;
     use crate::game::Entity;
+    use crate::migration::Migrator;
     use enumflags2::make_bitflags;
     use enumflags2::BitFlags;
-    use sea_orm::schema;
     use sea_orm::ActiveModelTrait;
     use sea_orm::ActiveValue;
-    use sea_orm::ConnectionTrait;
-    use sea_orm::Database;
+    use sea_orm::EntityTrait;
+    use sea_orm::ModelTrait;
+    use sea_orm::QueryFilter;
+    use sea_orm::QueryOrder;
+    use sea_orm_migration::MigratorTrait;
 
     use crate::entity::game::CompatibilityLayerItem;
 
+    use super::grade_order;
+    use super::grade_rank;
     use super::ActiveModel;
+    use super::ChildrenLink;
+    use super::Column;
     use super::Compatibility;
     use super::Model;
+    use super::ParentKind;
+    use super::ParentLink;
     use super::SupportLevel;
 
     #[test]
@@ -152,6 +403,7 @@ mod tests {
             compat: Compatibility {
                 0: BitFlags::default(),
             },
+            ..fixtures::game_model(1)
         };
         assert_eq!(game.name, "Test 1");
         assert_eq!(game.id, 1);
@@ -168,6 +420,7 @@ mod tests {
             compat: Compatibility {
                 0: BitFlags::default(),
             },
+            ..fixtures::game_model(1)
         };
         let grade = game.grading();
         assert_eq!(grade, "SSS");
@@ -179,6 +432,7 @@ mod tests {
             compat: Compatibility {
                 0: make_bitflags!(CompatibilityLayerItem::{WINE}),
             },
+            ..fixtures::game_model(2)
         };
         let grade = game.grading();
         assert_eq!(grade, "AA");
@@ -190,6 +444,7 @@ mod tests {
             compat: Compatibility {
                 0: make_bitflags!(CompatibilityLayerItem::{WINE | BOX64}),
             },
+            ..fixtures::game_model(3)
         };
         let grade = game.grading();
         assert_eq!(grade, "B");
@@ -201,6 +456,7 @@ mod tests {
             compat: Compatibility {
                 0: make_bitflags!(CompatibilityLayerItem::{LATX}),
             },
+            ..fixtures::game_model(4)
         };
         let grade = game.grading();
         assert_eq!(grade, "CC");
@@ -212,6 +468,7 @@ mod tests {
             compat: Compatibility {
                 0: make_bitflags!(CompatibilityLayerItem::{LATA}),
             },
+            ..fixtures::game_model(5)
         };
         let grade = game.grading();
         assert_eq!(grade, "DD");
@@ -219,9 +476,8 @@ mod tests {
 
     #[tokio::test]
     async fn games_write_db() {
-        let db = Database::connect("sqlite:///tmp/test.db?mode=rwc")
-            .await
-            .unwrap();
+        let (db, _guard) = crate::database::connect_test().await;
+        Migrator::up(&db, None).await.unwrap();
         let game = ActiveModel {
             name: ActiveValue::Set("Test 1".to_string()),
             id: ActiveValue::Set(1),
@@ -229,12 +485,154 @@ mod tests {
             compat: ActiveValue::Set(Compatibility {
                 0: BitFlags::default(),
             }),
+            ..Default::default()
         };
-        let builder = db.get_database_backend();
-        let schema = schema::Schema::new(builder);
-        db.execute(builder.build(&schema.create_table_from_entity(Entity)))
+        let _game = game.insert(&db).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn games_soft_delete() {
+        let (db, _guard) = crate::database::connect_test().await;
+        Migrator::up(&db, None).await.unwrap();
+        for (id, deleted_at) in [(1, None), (2, Some(chrono::Utc::now()))] {
+            let game = ActiveModel {
+                name: ActiveValue::Set(format!("Test {}", id)),
+                id: ActiveValue::Set(id),
+                supportlevel: ActiveValue::Set(SupportLevel::GOOD),
+                compat: ActiveValue::Set(Compatibility(BitFlags::default())),
+                deleted_at: ActiveValue::Set(deleted_at),
+                ..Default::default()
+            };
+            game.insert(&db).await.unwrap();
+        }
+        let visible = Entity::find_visible().all(&db).await.unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].id, 1);
+        assert_eq!(Entity::find().all(&db).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn games_parent() {
+        let (db, _guard) = crate::database::connect_test().await;
+        Migrator::up(&db, None).await.unwrap();
+        let mut games = Vec::new();
+        for (id, parent_id, parent_kind) in [
+            (1, None, None),
+            (2, Some(1), Some(ParentKind::DLC)),
+            (3, Some(1), Some(ParentKind::REMASTER)),
+        ] {
+            let game = ActiveModel {
+                name: ActiveValue::Set(format!("Test {}", id)),
+                id: ActiveValue::Set(id),
+                supportlevel: ActiveValue::Set(SupportLevel::GOOD),
+                compat: ActiveValue::Set(Compatibility(BitFlags::default())),
+                parent_id: ActiveValue::Set(parent_id),
+                parent_kind: ActiveValue::Set(parent_kind),
+                ..Default::default()
+            };
+            let game = game.insert(&db).await.unwrap();
+            assert_eq!(game.check_parent(&db).await.unwrap(), None);
+            games.push(game);
+        }
+        let children = games[0].find_linked(ChildrenLink).all(&db).await.unwrap();
+        assert_eq!(children.len(), 2);
+        let parent = games[1].find_linked(ParentLink).one(&db).await.unwrap();
+        assert_eq!(parent.unwrap().id, 1);
+
+        // 只允许一层，且关系种类必须与上级同时给出
+        let mut game = games[2].clone();
+        game.parent_id = Some(2);
+        assert!(game.check_parent(&db).await.unwrap().is_some());
+        game.parent_id = Some(3);
+        assert!(game.check_parent(&db).await.unwrap().is_some());
+        game.parent_id = Some(1);
+        game.parent_kind = None;
+        assert!(game.check_parent(&db).await.unwrap().is_some());
+        let mut game = games[0].clone();
+        game.parent_id = Some(3);
+        game.parent_kind = Some(ParentKind::PORT);
+        assert!(game.check_parent(&db).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn games_compat_filter() {
+        let (db, _guard) = crate::database::connect_test().await;
+        Migrator::up(&db, None).await.unwrap();
+        for (id, compat) in [
+            (1, make_bitflags!(CompatibilityLayerItem::{WINE | BOX64})),
+            (2, make_bitflags!(CompatibilityLayerItem::{WINE | LATX})),
+            (3, make_bitflags!(CompatibilityLayerItem::{BOX64})),
+        ] {
+            let game = ActiveModel {
+                name: ActiveValue::Set(format!("Test {}", id)),
+                id: ActiveValue::Set(id),
+                supportlevel: ActiveValue::Set(SupportLevel::GOOD),
+                compat: ActiveValue::Set(Compatibility(compat)),
+                ..Default::default()
+            };
+            game.insert(&db).await.unwrap();
+        }
+        let ids = |games: Vec<Model>| games.into_iter().map(|game| game.id).collect::<Vec<_>>();
+
+        let wine = Compatibility(make_bitflags!(CompatibilityLayerItem::{WINE}));
+        let box64 = Compatibility(make_bitflags!(CompatibilityLayerItem::{BOX64}));
+        let both = Compatibility(make_bitflags!(CompatibilityLayerItem::{WINE | BOX64}));
+        let games = Entity::find()
+            .filter(wine.contained_by_column())
+            .order_by_asc(Column::Id)
+            .all(&db)
             .await
             .unwrap();
-        let _game = game.insert(&db).await.unwrap();
+        assert_eq!(ids(games), vec![1, 2]);
+        let games = Entity::find()
+            .filter(both.contained_by_column())
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(ids(games), vec![1]);
+        let games = Entity::find()
+            .filter(wine.contained_by_column())
+            .filter(box64.excluded_by_column())
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(ids(games), vec![2]);
+    }
+
+    #[test]
+    fn games_patch() {
+        let game = fixtures::game_model(1);
+        let patch = serde_json::json!({ "id": 1, "supportlevel": "PERFECT", "compat": 8 });
+        let active = game.clone().patch(&patch).unwrap();
+        assert!(active.supportlevel.is_set());
+        assert!(active.compat.is_set());
+        assert!(!active.name.is_set());
+        assert!(!active.id.is_set());
+        assert_eq!(active.id.clone().unwrap(), 1);
+        assert_eq!(active.supportlevel.clone().unwrap(), SupportLevel::PERFECT);
+        assert_eq!(
+            active.compat.clone().unwrap().0,
+            make_bitflags!(CompatibilityLayerItem::{BOX64})
+        );
+
+        assert!(game
+            .clone()
+            .patch(&serde_json::json!({ "supportlevel": "BEST" }))
+            .is_err());
+        assert!(game.clone().patch(&serde_json::json!({ "id": 2 })).is_err());
+        assert!(game
+            .clone()
+            .patch(&serde_json::json!({ "confidence": 1.0 }))
+            .is_err());
+        assert!(game.patch(&serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn games_grade_order() {
+        let mut grades = vec!["B", "SS", "DD", "SSS", "AAA", "S", "?"];
+        grades.sort_by_key(|grade| grade_order(grade));
+        assert_eq!(grades, vec!["SSS", "SS", "S", "AAA", "B", "DD", "?"]);
+        let ranks: Vec<i32> = grades.iter().map(|grade| grade_rank(grade)).collect();
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
     }
 }
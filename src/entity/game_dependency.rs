@@ -0,0 +1,119 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 游戏所需的运行库及其版本要求。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "game_dependencies")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub gameid: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub dependencyid: i32,
+    /// 版本要求，如 ">=2.0, <2.4"，为空时任意版本均可。
+    pub version: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+    #[sea_orm(
+        belongs_to = "super::dependency::Entity",
+        from = "Column::Dependencyid",
+        to = "super::dependency::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Dependency,
+}
+
+impl Related<super::dependency::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Dependency.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 将版本要求拆成比较符与版本号，任一部分无法解析时返回 `None`。
+/// 各条件以逗号分隔，比较符可为 `>=`、`<=`、`>`、`<`、`=`，省略时视为 `=`。
+fn parse(constraint: &str) -> Option<Vec<(&str, Vec<u64>)>> {
+    constraint
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            let op = [">=", "<=", ">", "<", "="]
+                .into_iter()
+                .find(|op| part.starts_with(op))
+                .unwrap_or("=");
+            let version = part.strip_prefix(op).unwrap_or(part).trim();
+            let valid = !version.is_empty()
+                && version
+                    .split('.')
+                    .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+            valid.then(|| (op, crate::aggregation::version_key(version)))
+        })
+        .collect()
+}
+
+/// 版本要求能否解析。
+pub fn valid_constraint(constraint: &str) -> bool {
+    parse(constraint).is_some()
+}
+
+/// 给定版本是否满足版本要求，无法解析的要求视为不满足。缺少的版本号段按 0 处理，"2" 与 "2.0" 相同。
+pub fn satisfies(constraint: &str, version: &str) -> bool {
+    let Some(conditions) = parse(constraint) else {
+        return false;
+    };
+    let version = crate::aggregation::version_key(version);
+    conditions.iter().all(|(op, bound)| {
+        let length = version.len().max(bound.len());
+        let version = pad(&version, length);
+        match *op {
+            ">=" => version >= pad(bound, length),
+            "<=" => version <= pad(bound, length),
+            ">" => version > pad(bound, length),
+            "<" => version < pad(bound, length),
+            // 只比较要求中给出的部分，"=2" 可匹配 2.1
+            _ => version.starts_with(bound),
+        }
+    })
+}
+
+/// 在末尾补 0 至指定段数，`length` 不小于原有段数。
+fn pad(version: &[u64], length: usize) -> Vec<u64> {
+    let mut padded = version.to_vec();
+    padded.resize(length, 0);
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::satisfies;
+    use super::valid_constraint;
+
+    #[test]
+    fn dependencies_constraint() {
+        assert!(valid_constraint(">=2.0, <2.4"));
+        assert!(valid_constraint("4.8"));
+        assert!(!valid_constraint(">=two"));
+        assert!(!valid_constraint("1..2"));
+        assert!(!valid_constraint(""));
+
+        assert!(satisfies(">=2.0, <2.4", "2.3.1"));
+        assert!(!satisfies(">=2.0, <2.4", "2.4"));
+        assert!(satisfies("=4", "4.8"));
+        assert!(!satisfies("4.8", "4.7.2"));
+        assert!(!satisfies(">=two", "2.0"));
+        assert!(satisfies(">=2.0", "2"));
+        assert!(satisfies("=2.0", "2"));
+        assert!(!satisfies("=2.0", "2.1"));
+        assert!(satisfies("<=2", "2.0.0"));
+        assert!(!satisfies(">2", "2.0"));
+    }
+}
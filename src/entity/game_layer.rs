@@ -0,0 +1,180 @@
+use super::game::Compatibility;
+use super::layer;
+use enumflags2::BitFlags;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 兼容层对游戏的作用。
+#[derive(
+    PartialEq, Eq, Debug, Clone, Default, DeriveActiveEnum, EnumIter, Deserialize, Serialize,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum LayerRole {
+    /// 运行游戏必须使用该兼容层
+    #[default]
+    REQUIRED = 0,
+    /// 可以使用，但不用也能运行
+    OPTIONAL = 1,
+    /// 仅在该兼容层下测试过，不代表需要它
+    TESTED = 2,
+    /// 已知在该兼容层下无法运行
+    BROKEN = 3,
+}
+
+/// 游戏与兼容层登记表之间的多对多关联。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "game_layers")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub gameid: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub layerid: i32,
+    /// 未提供时视为必需。
+    #[serde(default)]
+    pub role: LayerRole,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+    #[sea_orm(
+        belongs_to = "super::layer::Entity",
+        from = "Column::Layerid",
+        to = "super::layer::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Layer,
+}
+
+impl Related<super::layer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Layer.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 关联记录及其对应的兼容层。
+pub type LayerLink = (Model, layer::Model);
+
+/// 查询若干游戏关联的兼容层及其作用。
+pub async fn find_links<C: ConnectionTrait>(
+    db: &C,
+    gameids: impl IntoIterator<Item = i32>,
+) -> Result<Vec<LayerLink>, DbErr> {
+    let links = Entity::find()
+        .filter(Column::Gameid.is_in(gameids))
+        .find_also_related(layer::Entity)
+        .all(db)
+        .await?;
+    Ok(links
+        .into_iter()
+        .filter_map(|(link, layer)| Some((link, layer?)))
+        .collect())
+}
+
+/// 由游戏当前登记为必需的兼容层重新计算旧版位标志并写入 `games.compat`，
+/// 关联、取消关联或修改作用后调用。
+pub async fn sync_compat<C: ConnectionTrait>(db: &C, gameid: i32) -> Result<(), DbErr> {
+    let links = find_links(db, [gameid]).await?;
+    let compat = Compatibility(BitFlags::all()).required(&links);
+    super::game::ActiveModel {
+        id: sea_orm::ActiveValue::Unchanged(gameid),
+        compat: sea_orm::ActiveValue::Set(compat),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+    Ok(())
+}
+
+impl Compatibility {
+    /// 只保留 `links` 中登记为必需的兼容层，作为评级需要考虑的部分。
+    /// 未登记或只登记为其他作用的兼容层不计入。
+    pub fn required(&self, links: &[LayerLink]) -> Self {
+        let required = links
+            .iter()
+            .filter(|(link, _)| link.role == LayerRole::REQUIRED)
+            .filter_map(|(_, layer)| layer.legacy_item())
+            .fold(BitFlags::empty(), |bits, item| bits | item);
+        Compatibility(self.0 & required)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::layer;
+    use crate::fixtures;
+    use enumflags2::make_bitflags;
+
+    use crate::entity::game::Compatibility;
+    use crate::entity::game::CompatibilityLayerItem;
+    use crate::entity::game::SupportLevel;
+
+    use super::LayerRole;
+    use super::Model;
+
+    fn link(layerid: i32, name: &str, version: &str, role: LayerRole) -> (Model, layer::Model) {
+        let link = Model {
+            gameid: 1,
+            layerid,
+            role,
+        };
+        let layer = layer::Model {
+            id: layerid,
+            name: name.to_string(),
+            kind: layer::LayerKind::TRANSLATOR,
+            version: Some(version.to_string()),
+        };
+        (link, layer)
+    }
+
+    #[test]
+    fn layers_required_only() {
+        let compat = Compatibility(make_bitflags!(CompatibilityLayerItem::{WINE | BOX64 | LATX}));
+        let links = vec![
+            link(1, "box64", "0.3.2", LayerRole::REQUIRED),
+            link(2, "wine", "9.0", LayerRole::TESTED),
+            link(3, "latx", "1.5", LayerRole::BROKEN),
+            // 同一兼容层的另一版本为必需时保留
+            link(4, "latx", "1.6", LayerRole::REQUIRED),
+        ];
+        assert_eq!(
+            compat.required(&links).0,
+            make_bitflags!(CompatibilityLayerItem::{BOX64 | LATX})
+        );
+        // 没有登记为必需的兼容层时不计入任何兼容层
+        assert!(compat.required(&[]).0.is_empty());
+
+        let game = crate::entity::game::Model {
+            supportlevel: SupportLevel::GREAT,
+            compat: Compatibility(make_bitflags!(CompatibilityLayerItem::{WINE | BOX64})),
+            ..fixtures::game_model(1)
+        };
+        assert_eq!(game.grading_with(&[], &[]), "AAA");
+        assert_eq!(game.grading_with(&[], &links), "AA");
+        let required = vec![
+            link(1, "box64", "0.3.2", LayerRole::REQUIRED),
+            link(2, "wine", "9.0", LayerRole::REQUIRED),
+        ];
+        assert_eq!(game.grading_with(&[], &required), "A");
+    }
+
+    #[test]
+    fn layers_role_json() {
+        let link: Model = serde_json::from_str(r#"{"gameid": 1, "layerid": 2}"#).unwrap();
+        assert_eq!(link.role, LayerRole::REQUIRED);
+        let json = serde_json::to_value(Model {
+            role: LayerRole::OPTIONAL,
+            ..link
+        })
+        .unwrap();
+        assert_eq!(json["role"], "OPTIONAL");
+    }
+}
@@ -0,0 +1,56 @@
+use super::game::{Compatibility, SupportLevel};
+use super::game_layer::LayerLink;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 游戏在某一兼容层组合下的运行效果，由该组合下的全部报告汇总得出。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "game_stacks")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub gameid: i32,
+    /// 报告关联的兼容层 id，升序并以逗号分隔，见 [`layer_key`]。
+    /// 为空表示报告未关联兼容层登记表，此时按 `compat` 区分组合。
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub layers: String,
+    /// 组合内各报告位标志的并集，为空表示原生运行。
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub compat: Compatibility,
+    pub supportlevel: SupportLevel,
+    /// 汇总时的置信度，范围为 0 到 1。
+    pub confidence: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 由报告关联的兼容层生成组合的键。
+pub fn layer_key(layers: &[super::layer::Model]) -> String {
+    let mut ids: Vec<i32> = layers.iter().map(|layer| layer.id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids.iter().map(i32::to_string).collect::<Vec<_>>().join(",")
+}
+
+impl Model {
+    /// 按当前生效的评级规则计算该组合的评级，只计游戏必需的兼容层。
+    pub fn grading(&self, links: &[LayerLink]) -> String {
+        crate::grading::rules().grade(&self.supportlevel, &self.compat.required(links), None)
+    }
+}
@@ -0,0 +1,218 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::FromJsonQueryResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 启动游戏前需要设置的环境变量，如 `BOX64_DYNAREC_BIGBLOCK`。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, FromJsonQueryResult)]
+pub struct EnvVars(pub BTreeMap<String, String>);
+
+/// 需要通过 winetricks 安装的组件，如 "vcrun2019"、"dxvk"。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, FromJsonQueryResult)]
+pub struct WinetricksVerbs(pub Vec<String>);
+
+/// 启动游戏的命令及其参数，如 `["wine", "Genshin Impact.exe"]`，每一项导出时作为一个参数。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, FromJsonQueryResult)]
+pub struct CommandArgs(pub Vec<String>);
+
+/// 运行某个游戏的配置指南，可以关联到验证过该配置的报告。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "guides")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub gameid: i32,
+    pub reportid: Option<i32>,
+    pub author: String,
+    pub title: String,
+    /// wine 前缀的路径，如 "~/.wine-genshin"；不需要 wine 时为空。
+    pub wine_prefix: Option<String>,
+    pub env: EnvVars,
+    pub winetricks: WinetricksVerbs,
+    /// 游戏的可执行文件名，如 "GenshinImpact.exe"，导出 box64rc 时作为段名。
+    pub executable: Option<String>,
+    /// 启动游戏的命令及其参数；为空时按兼容层拼出启动命令。
+    pub command: Option<CommandArgs>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+    #[sea_orm(
+        belongs_to = "super::report::Entity",
+        from = "Column::Reportid",
+        to = "super::report::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Report,
+    #[sea_orm(has_many = "super::guide_step::Entity")]
+    GuideStep,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl Related<super::report::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Report.def()
+    }
+}
+
+impl Related<super::guide_step::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GuideStep.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 环境变量名只能由字母、数字与下划线组成，且不能以数字开头。
+pub fn valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// winetricks 组件名只能由字母、数字与 `_`、`-`、`.`、`=` 组成。
+pub fn valid_winetricks_verb(verb: &str) -> bool {
+    !verb.is_empty()
+        && verb
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '='))
+}
+
+/// 可执行文件名不能包含路径分隔符、方括号或控制字符，以便写入 box64rc 的段名。
+pub fn valid_executable(name: &str) -> bool {
+    !name.is_empty()
+        && !name
+            .chars()
+            .any(|c| c.is_control() || matches!(c, '/' | '\\' | '[' | ']'))
+}
+
+/// 命令不能为空，各参数不能包含控制字符。环境变量应写在 `env` 中，不能以 `NAME=value` 开头。
+pub fn valid_command(args: &[String]) -> bool {
+    let Some(program) = args.first() else {
+        return false;
+    };
+    let assignment = program
+        .split_once('=')
+        .is_some_and(|(name, _)| valid_env_name(name));
+    !program.is_empty()
+        && !assignment
+        && !args.iter().any(|arg| arg.chars().any(|c| c.is_control()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::guide;
+    use crate::entity::guide_step;
+    use crate::fixtures;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::EntityTrait;
+    use sea_orm::ModelTrait;
+    use sea_orm::QueryOrder;
+    use std::collections::BTreeMap;
+
+    use super::valid_command;
+    use super::valid_env_name;
+    use super::valid_executable;
+    use super::valid_winetricks_verb;
+    use super::CommandArgs;
+    use super::EnvVars;
+    use super::WinetricksVerbs;
+
+    #[test]
+    fn guides_validate() {
+        assert!(valid_env_name("BOX64_DYNAREC_BIGBLOCK"));
+        assert!(valid_env_name("_X1"));
+        assert!(!valid_env_name("1X"));
+        assert!(!valid_env_name("A=B"));
+        assert!(!valid_env_name(""));
+        assert!(valid_winetricks_verb("vcrun2019"));
+        assert!(valid_winetricks_verb("renderer=vulkan"));
+        assert!(!valid_winetricks_verb("dxvk; rm -rf ~"));
+        assert!(valid_executable("Genshin Impact.exe"));
+        assert!(!valid_executable("bin/game.exe"));
+        assert!(!valid_executable("game]\n[other"));
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert!(valid_command(&args(&[
+            "wine",
+            "Genshin Impact.exe",
+            "-dx11"
+        ])));
+        assert!(!valid_command(&args(&["./game.exe\nrm -rf ~"])));
+        assert!(!valid_command(&args(&[
+            "WINEDEBUG=-all",
+            "wine",
+            "game.exe"
+        ])));
+        assert!(!valid_command(&args(&[""])));
+        assert!(!valid_command(&[]));
+    }
+
+    #[tokio::test]
+    async fn guides_write_db() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = fixtures::game(1);
+        game.insert(&db).await.unwrap();
+        let guide = guide::ActiveModel {
+            gameid: ActiveValue::Set(1),
+            author: ActiveValue::Set("alice".to_string()),
+            title: ActiveValue::Set("box64 + wine".to_string()),
+            wine_prefix: ActiveValue::Set(Some("~/.wine-test".to_string())),
+            env: ActiveValue::Set(EnvVars(BTreeMap::from([(
+                "BOX64_DYNAREC_BIGBLOCK".to_string(),
+                "0".to_string(),
+            )]))),
+            winetricks: ActiveValue::Set(WinetricksVerbs(vec!["vcrun2019".to_string()])),
+            executable: ActiveValue::Set(Some("game.exe".to_string())),
+            command: ActiveValue::Set(Some(CommandArgs(vec![
+                "box64".to_string(),
+                "wine64".to_string(),
+                "game.exe".to_string(),
+            ]))),
+            created_at: ActiveValue::Set(chrono::Utc::now()),
+            ..Default::default()
+        };
+        let guide = guide.insert(&db).await.unwrap();
+        for (position, body) in ["Install wine.", "Run winetricks."].into_iter().enumerate() {
+            let step = guide_step::ActiveModel {
+                guideid: ActiveValue::Set(guide.id),
+                position: ActiveValue::Set(position as i32),
+                body: ActiveValue::Set(body.to_string()),
+                body_html: ActiveValue::Set(format!("<p>{}</p>", body)),
+            };
+            step.insert(&db).await.unwrap();
+        }
+
+        let guide = guide::Entity::find_by_id(guide.id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(guide.env.0["BOX64_DYNAREC_BIGBLOCK"], "0");
+        assert_eq!(guide.winetricks.0, vec!["vcrun2019"]);
+        let steps = guide
+            .find_related(guide_step::Entity)
+            .order_by_asc(guide_step::Column::Position)
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].body, "Run winetricks.");
+    }
+}
@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 指南中的一个步骤，按 `position` 从小到大排列。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "guide_steps")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub guideid: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub position: i32,
+    /// 去掉原始 HTML 后的 Markdown。
+    pub body: String,
+    /// 由 `body` 渲染并清理后的 HTML，可直接展示。
+    pub body_html: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::guide::Entity",
+        from = "Column::Guideid",
+        to = "super::guide::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Guide,
+}
+
+impl Related<super::guide::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Guide.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,110 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::JsonValue;
+use serde::{Deserialize, Serialize};
+
+/// 测试所用的龙芯机器，例如 3A6000 + XA61200 主板。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "hardware")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// CPU 型号，如 "3A5000"、"3A6000"、"3C5000"、"2K2000"。
+    pub cpu: String,
+    pub board: Option<String>,
+    /// 内存容量，单位为 MiB。
+    pub ram: Option<i32>,
+    pub gpu: Option<String>,
+    /// 登记该机器的用户，只有该用户与管理员可以修改或删除；早期登记的机器为空。
+    pub owner: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::report::Entity")]
+    Report,
+}
+
+impl Related<super::report::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Report.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 将部分字段的 JSON 应用到机器上，只有 JSON 中出现的字段会被标记为已修改。
+    /// `id` 与 `owner` 不能通过此方式修改，与原值不同时返回错误。
+    pub fn patch(self, patch: &JsonValue) -> Result<ActiveModel, DbErr> {
+        super::patch::apply(self, patch, &[Column::Id, Column::Owner])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::hardware;
+    use crate::fixtures;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::ColumnTrait;
+    use sea_orm::EntityTrait;
+    use sea_orm::QueryFilter;
+
+    #[tokio::test]
+    async fn hardware_write_db() {
+        let (db, _guard) = fixtures::setup().await;
+        for cpu in ["3A5000", "3A6000"] {
+            let hardware = hardware::ActiveModel {
+                cpu: ActiveValue::Set(cpu.to_string()),
+                board: ActiveValue::Set(None),
+                ram: ActiveValue::Set(Some(16384)),
+                gpu: ActiveValue::Set(Some("AMD Radeon RX 550".to_string())),
+                ..Default::default()
+            };
+            hardware.insert(&db).await.unwrap();
+        }
+        let found = hardware::Entity::find()
+            .filter(hardware::Column::Cpu.eq("3A6000"))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, 2);
+        assert_eq!(found[0].ram, Some(16384));
+    }
+
+    #[test]
+    fn hardware_patch() {
+        let hardware = hardware::Model {
+            id: 1,
+            cpu: "3A6000".to_string(),
+            board: Some("XA61200".to_string()),
+            ram: Some(16384),
+            gpu: None,
+            owner: Some("alice".to_string()),
+        };
+        let patch = serde_json::json!({ "id": 1, "ram": 32768, "owner": "alice" });
+        let active = hardware.clone().patch(&patch).unwrap();
+        assert!(active.ram.is_set());
+        assert!(!active.board.is_set());
+        assert!(!active.id.is_set());
+        assert!(!active.owner.is_set());
+        assert_eq!(active.ram.clone().unwrap(), Some(32768));
+        assert_eq!(active.board.clone().unwrap(), Some("XA61200".to_string()));
+        assert_eq!(active.owner.clone().unwrap(), Some("alice".to_string()));
+
+        let active = hardware
+            .clone()
+            .patch(&serde_json::json!({ "gpu": "AMD Radeon RX 550", "board": null }))
+            .unwrap();
+        assert!(active.gpu.is_set());
+        assert_eq!(active.board.clone().unwrap(), None);
+        assert!(hardware
+            .clone()
+            .patch(&serde_json::json!({ "owner": "bob" }))
+            .is_err());
+        assert!(hardware
+            .patch(&serde_json::json!({ "ram": "lots" }))
+            .is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 问题的严重程度。
+#[derive(PartialEq, Eq, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum Severity {
+    /// 无法进入游戏或必然崩溃
+    CRITICAL = 0,
+    /// 影响正常游玩，如没有声音、过场动画无法播放
+    MAJOR = 1,
+    /// 偶发或不影响游玩
+    MINOR = 2,
+    /// 仅影响显示效果
+    COSMETIC = 3,
+}
+
+/// 游戏的已知问题及其解决方法。`fixed_in` 为空的问题视为尚未解决。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "issues")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub gameid: i32,
+    pub reporter: String,
+    pub title: String,
+    pub severity: Severity,
+    /// 去掉原始 HTML 后的 Markdown。
+    pub workaround: Option<String>,
+    /// 上游缺陷追踪中的链接，仅允许 http 与 https。
+    pub bug_url: Option<String>,
+    /// 修复该问题的版本，如 "box64 0.3.2"。
+    pub fixed_in: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl Related<super::layer::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::issue_layer::Relation::Layer.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::issue_layer::Relation::Issue.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// 查询尚未解决的问题。
+    pub fn find_open() -> Select<Entity> {
+        Self::find().filter(Column::FixedIn.is_null())
+    }
+}
+
+/// 链接必须以 http:// 或 https:// 开头，且不能包含空白字符。
+pub fn valid_bug_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://"))
+        && !url.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::issue;
+    use crate::entity::issue_layer;
+    use crate::entity::layer;
+    use crate::fixtures;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::ColumnTrait;
+    use sea_orm::ModelTrait;
+    use sea_orm::QueryFilter;
+
+    use super::valid_bug_url;
+    use super::Entity;
+    use super::Severity;
+
+    #[test]
+    fn issues_bug_url() {
+        assert!(valid_bug_url("https://github.com/ptitSeb/box64/issues/1"));
+        assert!(!valid_bug_url("javascript:alert(1)"));
+        assert!(!valid_bug_url("https://example.com/a b"));
+    }
+
+    #[tokio::test]
+    async fn issues_open_only() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = fixtures::game(1);
+        game.insert(&db).await.unwrap();
+        let layer = layer::ActiveModel {
+            name: ActiveValue::Set("box64".to_string()),
+            kind: ActiveValue::Set(layer::LayerKind::TRANSLATOR),
+            version: ActiveValue::Set(Some("0.3.0".to_string())),
+            ..Default::default()
+        };
+        let layer = layer.insert(&db).await.unwrap();
+        for (title, fixed_in) in [("No audio", None), ("Crash in menu", Some("box64 0.3.2"))] {
+            let issue = issue::ActiveModel {
+                gameid: ActiveValue::Set(1),
+                reporter: ActiveValue::Set("alice".to_string()),
+                title: ActiveValue::Set(title.to_string()),
+                severity: ActiveValue::Set(Severity::MAJOR),
+                fixed_in: ActiveValue::Set(fixed_in.map(|v| v.to_string())),
+                created_at: ActiveValue::Set(chrono::Utc::now()),
+                ..Default::default()
+            };
+            let issue = issue.insert(&db).await.unwrap();
+            let link = issue_layer::ActiveModel {
+                issueid: ActiveValue::Set(issue.id),
+                layerid: ActiveValue::Set(layer.id),
+            };
+            link.insert(&db).await.unwrap();
+        }
+
+        let open = Entity::find_open()
+            .filter(issue::Column::Gameid.eq(1))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].title, "No audio");
+        let layers = open[0].find_related(layer::Entity).all(&db).await.unwrap();
+        assert_eq!(layers[0].version.as_deref(), Some("0.3.0"));
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 问题与受影响的兼容层版本之间的多对多关联。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "issue_layers")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub issueid: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub layerid: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::issue::Entity",
+        from = "Column::Issueid",
+        to = "super::issue::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Issue,
+    #[sea_orm(
+        belongs_to = "super::layer::Entity",
+        from = "Column::Layerid",
+        to = "super::layer::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Layer,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,163 @@
+use super::game::{Compatibility, CompatibilityLayerItem};
+use enumflags2::BitFlags;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 兼容层的种类。
+#[derive(PartialEq, Eq, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum LayerKind {
+    /// 二进制转译器，如 LATX、LATA、box64、FEX
+    TRANSLATOR = 0,
+    /// 整机或用户态模拟器，如 qemu-user
+    EMULATOR = 1,
+    /// Windows API 兼容层，如 wine
+    WINE = 2,
+    /// 图形 API 转换层，如 DXVK
+    GRAPHICS = 3,
+    /// 原生 LoongArch 构建
+    NATIVE = 4,
+}
+
+/// 兼容层登记表，同一兼容层的不同版本各占一行。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "layers")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub kind: LayerKind,
+    pub version: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::game_layer::Relation::Game.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::game_layer::Relation::Layer.def().rev())
+    }
+}
+
+impl Related<super::report::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::report_layer::Relation::Report.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::report_layer::Relation::Layer.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 对应的旧版位标志，登记表中新增的兼容层没有对应项。
+    pub fn legacy_item(&self) -> Option<CompatibilityLayerItem> {
+        match self.name.to_lowercase().as_str() {
+            "wine" => Some(CompatibilityLayerItem::WINE),
+            "latx" => Some(CompatibilityLayerItem::LATX),
+            "lata" => Some(CompatibilityLayerItem::LATA),
+            "box64" => Some(CompatibilityLayerItem::BOX64),
+            _ => None,
+        }
+    }
+}
+
+impl Compatibility {
+    /// 由登记表中的兼容层推导出旧版位标志，以便旧字段与关联表保持一致。
+    pub fn from_layers(layers: &[Model]) -> Self {
+        let bits = layers
+            .iter()
+            .filter_map(|layer| layer.legacy_item())
+            .fold(BitFlags::empty(), |bits, item| bits | item);
+        Compatibility(bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::game;
+    use crate::entity::game_layer;
+    use crate::entity::layer;
+    use crate::fixtures;
+    use enumflags2::make_bitflags;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::ModelTrait;
+
+    use crate::entity::game::Compatibility;
+    use crate::entity::game::CompatibilityLayerItem;
+    use crate::entity::game::SupportLevel;
+
+    use super::LayerKind;
+    use super::Model;
+
+    #[test]
+    fn layers_to_legacy() {
+        let layers = vec![
+            Model {
+                id: 1,
+                name: "box64".to_string(),
+                kind: LayerKind::TRANSLATOR,
+                version: Some("0.3.2".to_string()),
+            },
+            Model {
+                id: 2,
+                name: "Wine".to_string(),
+                kind: LayerKind::WINE,
+                version: Some("9.0".to_string()),
+            },
+            Model {
+                id: 3,
+                name: "DXVK".to_string(),
+                kind: LayerKind::GRAPHICS,
+                version: Some("2.4".to_string()),
+            },
+        ];
+        let compat = Compatibility::from_layers(&layers);
+        assert_eq!(
+            compat.0,
+            make_bitflags!(CompatibilityLayerItem::{WINE | BOX64})
+        );
+        assert!(Compatibility::from_layers(&[]).0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn layers_link_games() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = game::ActiveModel {
+            supportlevel: ActiveValue::Set(SupportLevel::GREAT),
+            ..fixtures::game(1)
+        };
+        let game = game.insert(&db).await.unwrap();
+        for (name, kind, version) in [
+            ("box64", LayerKind::TRANSLATOR, "0.3.2"),
+            ("wine", LayerKind::WINE, "9.0"),
+        ] {
+            let layer = layer::ActiveModel {
+                name: ActiveValue::Set(name.to_string()),
+                kind: ActiveValue::Set(kind),
+                version: ActiveValue::Set(Some(version.to_string())),
+                ..Default::default()
+            };
+            let layer = layer.insert(&db).await.unwrap();
+            let link = game_layer::ActiveModel {
+                gameid: ActiveValue::Set(game.id),
+                layerid: ActiveValue::Set(layer.id),
+                role: ActiveValue::Set(game_layer::LayerRole::REQUIRED),
+            };
+            link.insert(&db).await.unwrap();
+        }
+
+        let layers = game.find_related(layer::Entity).all(&db).await.unwrap();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].version.as_deref(), Some("0.3.2"));
+        let games = layers[1].find_related(game::Entity).all(&db).await.unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Test 1");
+    }
+}
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{IntoActiveModel, Iterable, JsonValue};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// 将部分字段的 JSON 应用到模型上，只有 JSON 中出现的字段会被标记为已修改。
+/// `readonly` 中的字段与原值不同时返回错误；与原值相同时忽略，以便客户端原样提交整个模型。
+pub fn apply<M, A>(
+    model: M,
+    patch: &JsonValue,
+    readonly: &[<M::Entity as EntityTrait>::Column],
+) -> Result<A, DbErr>
+where
+    M: ModelTrait + IntoActiveModel<A> + Serialize + DeserializeOwned,
+    A: ActiveModelTrait<Entity = M::Entity>,
+{
+    let Some(fields) = patch.as_object() else {
+        return Err(DbErr::Json("Patch must be a JSON object.".to_string()));
+    };
+    let mut merged = serde_json::to_value(&model).map_err(|e| DbErr::Json(e.to_string()))?;
+    for (key, value) in fields {
+        merged[key] = value.clone();
+    }
+    let patched: M = serde_json::from_value(merged).map_err(|e| DbErr::Json(e.to_string()))?;
+    let mut active = model.into_active_model();
+    for column in <M::Entity as EntityTrait>::Column::iter() {
+        let name = column.to_string();
+        if !fields.contains_key(&name) {
+            continue;
+        }
+        if readonly.iter().any(|readonly| readonly.to_string() == name) {
+            if active.get(column).into_value() != Some(patched.get(column)) {
+                return Err(DbErr::Custom(format!("Field {} cannot be updated.", name)));
+            }
+            continue;
+        }
+        active.set(column, patched.get(column));
+    }
+    Ok(active)
+}
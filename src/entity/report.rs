@@ -0,0 +1,177 @@
+use super::game::{Abi, Compatibility, SupportLevel};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 测试者针对某个游戏提交的兼容性报告，同一游戏可以有多份报告。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub gameid: i32,
+    pub tester: String,
+    pub supportlevel: SupportLevel,
+    pub compat: Compatibility,
+    pub hardwareid: Option<i32>,
+    pub distributionid: Option<i32>,
+    /// 测试的游戏版本，未指明时为空。
+    pub buildid: Option<i32>,
+    pub abi: Option<Abi>,
+    pub kernel: Option<String>,
+    pub comment: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::game::Entity",
+        from = "Column::Gameid",
+        to = "super::game::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Game,
+    #[sea_orm(
+        belongs_to = "super::hardware::Entity",
+        from = "Column::Hardwareid",
+        to = "super::hardware::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Hardware,
+    #[sea_orm(
+        belongs_to = "super::distribution::Entity",
+        from = "Column::Distributionid",
+        to = "super::distribution::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Distribution,
+    #[sea_orm(
+        belongs_to = "super::build::Entity",
+        from = "Column::Buildid",
+        to = "super::build::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Build,
+}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Game.def()
+    }
+}
+
+impl Related<super::hardware::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Hardware.def()
+    }
+}
+
+impl Related<super::distribution::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Distribution.def()
+    }
+}
+
+impl Related<super::build::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Build.def()
+    }
+}
+
+impl Related<super::layer::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::report_layer::Relation::Layer.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::report_layer::Relation::Report.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 按当前生效的评级规则计算本报告的评级，计入报告的 ABI。
+    pub fn grading(&self) -> String {
+        crate::grading::rules().grade(&self.supportlevel, &self.compat, self.abi.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::distribution;
+    use crate::entity::game;
+    use crate::entity::report;
+    use crate::fixtures;
+    use enumflags2::make_bitflags;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::ColumnTrait;
+    use sea_orm::EntityTrait;
+    use sea_orm::ModelTrait;
+    use sea_orm::QueryFilter;
+
+    use crate::entity::game::Abi;
+    use crate::entity::game::Compatibility;
+    use crate::entity::game::CompatibilityLayerItem;
+    use crate::entity::game::SupportLevel;
+
+    #[tokio::test]
+    async fn reports_write_db() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = game::ActiveModel {
+            supportlevel: ActiveValue::Set(SupportLevel::PERFECT),
+            ..fixtures::game(1)
+        };
+        let game = game.insert(&db).await.unwrap();
+        let distribution = distribution::ActiveModel {
+            name: ActiveValue::Set("AOSC OS".to_string()),
+            version: ActiveValue::Set(None),
+            ..Default::default()
+        };
+        let distribution = distribution.insert(&db).await.unwrap();
+        for (tester, level, abi) in [
+            ("alice", SupportLevel::GREAT, Abi::NEWWORLD),
+            ("bob", SupportLevel::BAD, Abi::OLDWORLD),
+        ] {
+            let report = report::ActiveModel {
+                gameid: ActiveValue::Set(game.id),
+                tester: ActiveValue::Set(tester.to_string()),
+                supportlevel: ActiveValue::Set(level),
+                compat: ActiveValue::Set(Compatibility(
+                    make_bitflags!(CompatibilityLayerItem::{WINE | BOX64}),
+                )),
+                hardwareid: ActiveValue::Set(None),
+                distributionid: ActiveValue::Set(Some(distribution.id)),
+                abi: ActiveValue::Set(Some(abi)),
+                kernel: ActiveValue::Set(Some("6.12.0".to_string())),
+                comment: ActiveValue::Set(None),
+                created_at: ActiveValue::Set(chrono::Utc::now()),
+                ..Default::default()
+            };
+            report.insert(&db).await.unwrap();
+        }
+
+        let reports = game.find_related(report::Entity).all(&db).await.unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].tester, "alice");
+        assert_eq!(reports[1].supportlevel, SupportLevel::BAD);
+        let newworld = distribution
+            .find_related(report::Entity)
+            .filter(report::Column::Abi.eq(Abi::NEWWORLD))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(newworld.len(), 1);
+        assert_eq!(newworld[0].tester, "alice");
+        let owner = report::Entity::find_by_id(reports[0].id)
+            .find_also_related(game::Entity)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .1
+            .unwrap();
+        assert_eq!(owner.name, "Test 1");
+    }
+}
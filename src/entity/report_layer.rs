@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 报告与兼容层登记表之间的多对多关联，记录测试时实际使用的兼容层版本。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "report_layers")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub reportid: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub layerid: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::report::Entity",
+        from = "Column::Reportid",
+        to = "super::report::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Report,
+    #[sea_orm(
+        belongs_to = "super::layer::Entity",
+        from = "Column::Layerid",
+        to = "super::layer::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Layer,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
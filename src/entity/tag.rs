@@ -0,0 +1,92 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 标签的种类，类型（genre）与一般标签分开管理。
+#[derive(PartialEq, Eq, Debug, Clone, DeriveActiveEnum, EnumIter, Deserialize, Serialize)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+pub enum TagKind {
+    GENRE = 0,
+    TAG = 1,
+}
+
+/// 游戏的类型与标签，如 "RPG"、"视觉小说"、"多人"。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "tags")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub name: String,
+    pub kind: TagKind,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Related<super::game::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::game_tag::Relation::Game.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::game_tag::Relation::Tag.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::game;
+    use crate::entity::game_tag;
+    use crate::entity::tag;
+    use crate::fixtures;
+    use sea_orm::ActiveModelTrait;
+    use sea_orm::ActiveValue;
+    use sea_orm::ColumnTrait;
+    use sea_orm::EntityTrait;
+    use sea_orm::ModelTrait;
+    use sea_orm::QueryFilter;
+
+    use crate::entity::game::SupportLevel;
+
+    use super::TagKind;
+
+    #[tokio::test]
+    async fn tags_link_games() {
+        let (db, _guard) = fixtures::setup().await;
+
+        let game = game::ActiveModel {
+            supportlevel: ActiveValue::Set(SupportLevel::GREAT),
+            developer: ActiveValue::Set(Some("Test Studio".to_string())),
+            release_year: ActiveValue::Set(Some(2020)),
+            ..fixtures::game(1)
+        };
+        let game = game.insert(&db).await.unwrap();
+        for (name, kind) in [("RPG", TagKind::GENRE), ("多人", TagKind::TAG)] {
+            let tag = tag::ActiveModel {
+                name: ActiveValue::Set(name.to_string()),
+                kind: ActiveValue::Set(kind),
+                ..Default::default()
+            };
+            let tag = tag.insert(&db).await.unwrap();
+            let link = game_tag::ActiveModel {
+                gameid: ActiveValue::Set(game.id),
+                tagid: ActiveValue::Set(tag.id),
+            };
+            link.insert(&db).await.unwrap();
+        }
+
+        let tags = game.find_related(tag::Entity).all(&db).await.unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].kind, TagKind::GENRE);
+        let games = game::Entity::find()
+            .inner_join(tag::Entity)
+            .filter(tag::Column::Name.eq("多人"))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].developer.as_deref(), Some("Test Studio"));
+        assert_eq!(games[0].publisher, None);
+    }
+}
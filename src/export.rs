@@ -0,0 +1,381 @@
+use crate::entity::game::{self, Compatibility, CompatibilityLayerItem};
+use crate::entity::guide;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 可导出的配置格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// box64 的按程序配置，可追加到 `~/.box64rc`
+    BOX64RC,
+    /// 设置环境变量后启动游戏的 shell 脚本
+    LAUNCHER,
+    /// Lutris 安装脚本
+    LUTRIS,
+}
+
+impl ExportFormat {
+    /// 响应的 MIME 类型。
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::BOX64RC => "text/plain; charset=utf-8",
+            ExportFormat::LAUNCHER => "text/x-shellscript; charset=utf-8",
+            ExportFormat::LUTRIS => "application/yaml; charset=utf-8",
+        }
+    }
+
+    /// 下载时建议的文件名。
+    pub fn file_name(&self, game: &game::Model) -> String {
+        let slug = slug(&game.name);
+        match self {
+            ExportFormat::BOX64RC => format!("{}.box64rc", slug),
+            ExportFormat::LAUNCHER => format!("{}.sh", slug),
+            ExportFormat::LUTRIS => format!("{}.yml", slug),
+        }
+    }
+
+    /// 生成导出内容。`compat` 为游戏必需的兼容层，没有指南时只能给出最基本的配置。
+    pub fn render(
+        &self,
+        game: &game::Model,
+        compat: &Compatibility,
+        guide: Option<&guide::Model>,
+    ) -> Result<String, String> {
+        match self {
+            ExportFormat::BOX64RC => Ok(box64rc(game, guide)),
+            ExportFormat::LAUNCHER => Ok(launcher(game, compat, guide)),
+            ExportFormat::LUTRIS => lutris(game, compat, guide),
+        }
+    }
+}
+
+/// 由游戏名生成只含小写字母、数字与连字符的标识。
+pub fn slug(name: &str) -> String {
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "game".to_string()
+    } else {
+        slug
+    }
+}
+
+/// 用单引号包裹，使其在 shell 中按原样解释。
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// 写入注释行前把控制字符换成空格，避免换行把游戏名之后的内容变成配置或命令。
+fn comment(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// 逐个参数加引号，使其中的空格与 shell 元字符不被解释。
+fn quote_command(args: &guide::CommandArgs) -> String {
+    args.0
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn executable(guide: Option<&guide::Model>) -> &str {
+    guide
+        .and_then(|guide| guide.executable.as_deref())
+        .unwrap_or("game.exe")
+}
+
+fn env(guide: Option<&guide::Model>) -> BTreeMap<String, String> {
+    guide.map(|guide| guide.env.0.clone()).unwrap_or_default()
+}
+
+/// 指南未给出命令行时，按必需的兼容层拼出启动命令。
+fn default_command(compat: &Compatibility, executable: &str) -> String {
+    let mut command = Vec::new();
+    if compat.0.contains(CompatibilityLayerItem::BOX64) {
+        command.push("box64");
+    } else if compat.0.contains(CompatibilityLayerItem::LATX) {
+        command.push("latx-x86_64");
+    } else if compat.0.contains(CompatibilityLayerItem::LATA) {
+        command.push("lata-x86_64");
+    }
+    if compat.0.contains(CompatibilityLayerItem::WINE) {
+        command.push("wine");
+    }
+    let mut command = command.join(" ");
+    if !command.is_empty() {
+        command.push(' ');
+    }
+    command + &shell_quote(&format!("./{}", executable))
+}
+
+/// box64rc 中以可执行文件名为段名，只包含 `BOX64_` 开头的环境变量。
+fn box64rc(game: &game::Model, guide: Option<&guide::Model>) -> String {
+    let mut rc = format!("# {}\n[{}]\n", comment(&game.name), executable(guide));
+    for (name, value) in env(guide) {
+        if name.starts_with("BOX64_") {
+            rc.push_str(&format!("{}={}\n", name, value));
+        }
+    }
+    rc
+}
+
+fn launcher(game: &game::Model, compat: &Compatibility, guide: Option<&guide::Model>) -> String {
+    let mut script = format!("#!/bin/sh\n# {}\nset -e\n\n", comment(&game.name));
+    let wine = compat.0.contains(CompatibilityLayerItem::WINE);
+    if let Some(prefix) = guide.and_then(|guide| guide.wine_prefix.as_deref()) {
+        // 允许使用 ~ 表示家目录
+        let prefix = match prefix.strip_prefix("~/") {
+            Some(rest) => format!("\"$HOME\"/{}", shell_quote(rest)),
+            None => shell_quote(prefix),
+        };
+        script.push_str(&format!("export WINEPREFIX={}\n", prefix));
+    }
+    for (name, value) in env(guide) {
+        script.push_str(&format!("export {}={}\n", name, shell_quote(&value)));
+    }
+    let verbs = guide
+        .map(|guide| guide.winetricks.0.clone())
+        .unwrap_or_default();
+    if wine && !verbs.is_empty() {
+        script.push_str(&format!(
+            "\n# 首次运行时安装所需组件\nif [ ! -d \"${{WINEPREFIX:-$HOME/.wine}}\" ]; then\n    winetricks -q {}\nfi\n",
+            verbs.join(" ")
+        ));
+    }
+    let command = guide
+        .and_then(|guide| guide.command.as_ref())
+        .filter(|command| !command.0.is_empty())
+        .map(quote_command)
+        .unwrap_or_else(|| default_command(compat, executable(guide)));
+    script.push_str(&format!("\nexec {}\n", command));
+    script
+}
+
+#[derive(Serialize)]
+struct LutrisInstaller {
+    name: String,
+    game_slug: String,
+    version: String,
+    slug: String,
+    runner: &'static str,
+    script: LutrisScript,
+}
+
+#[derive(Serialize)]
+struct LutrisScript {
+    game: LutrisGame,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    installer: Vec<BTreeMap<&'static str, LutrisTask>>,
+    system: LutrisSystem,
+}
+
+#[derive(Serialize)]
+struct LutrisGame {
+    exe: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+}
+
+#[derive(Serialize)]
+struct LutrisTask {
+    name: &'static str,
+    prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    app: Option<String>,
+}
+
+#[derive(Serialize)]
+struct LutrisSystem {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    env: BTreeMap<String, String>,
+    /// 转译器作为启动命令的前缀
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix_command: Option<String>,
+}
+
+fn lutris(
+    game: &game::Model,
+    compat: &Compatibility,
+    guide: Option<&guide::Model>,
+) -> Result<String, String> {
+    let wine = compat.0.contains(CompatibilityLayerItem::WINE);
+    let prefix = wine.then(|| "$GAMEDIR".to_string());
+    let mut installer = Vec::new();
+    if wine {
+        installer.push(BTreeMap::from([(
+            "task",
+            LutrisTask {
+                name: "create_prefix",
+                prefix: "$GAMEDIR".to_string(),
+                app: None,
+            },
+        )]));
+        let verbs = guide
+            .map(|guide| guide.winetricks.0.clone())
+            .unwrap_or_default();
+        if !verbs.is_empty() {
+            installer.push(BTreeMap::from([(
+                "task",
+                LutrisTask {
+                    name: "winetricks",
+                    prefix: "$GAMEDIR".to_string(),
+                    app: Some(verbs.join(" ")),
+                },
+            )]));
+        }
+    }
+    let translator = [
+        (CompatibilityLayerItem::BOX64, "box64"),
+        (CompatibilityLayerItem::LATX, "latx-x86_64"),
+        (CompatibilityLayerItem::LATA, "lata-x86_64"),
+    ]
+    .into_iter()
+    .find(|(item, _)| compat.0.contains(*item))
+    .map(|(_, command)| command.to_string());
+    let slug = slug(&game.name);
+    let installer = LutrisInstaller {
+        name: game.name.clone(),
+        game_slug: slug.clone(),
+        version: "LoongGameDB".to_string(),
+        slug: format!("{}-loonggamedb", slug),
+        runner: if wine { "wine" } else { "linux" },
+        script: LutrisScript {
+            game: LutrisGame {
+                exe: format!("$GAMEDIR/{}", executable(guide)),
+                prefix,
+            },
+            installer,
+            system: LutrisSystem {
+                env: env(guide),
+                prefix_command: translator,
+            },
+        },
+    };
+    serde_yaml::to_string(&installer).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::game;
+    use crate::entity::guide;
+    use crate::fixtures;
+    use enumflags2::make_bitflags;
+    use std::collections::BTreeMap;
+
+    use crate::entity::game::Compatibility;
+    use crate::entity::game::CompatibilityLayerItem;
+
+    use super::slug;
+    use super::ExportFormat;
+
+    fn fixture() -> (game::Model, Compatibility, guide::Model) {
+        let compat = Compatibility(make_bitflags!(CompatibilityLayerItem::{WINE | BOX64}));
+        let game = game::Model {
+            name: "Genshin Impact".to_string(),
+            compat,
+            ..fixtures::game_model(1)
+        };
+        let guide = guide::Model {
+            id: 1,
+            gameid: 1,
+            reportid: None,
+            author: "alice".to_string(),
+            title: "box64 + wine".to_string(),
+            wine_prefix: Some("~/.wine-genshin".to_string()),
+            env: guide::EnvVars(BTreeMap::from([
+                ("BOX64_DYNAREC_BIGBLOCK".to_string(), "0".to_string()),
+                ("DXVK_HUD".to_string(), "fps's".to_string()),
+            ])),
+            winetricks: guide::WinetricksVerbs(vec!["vcrun2019".to_string()]),
+            executable: Some("GenshinImpact.exe".to_string()),
+            command: None,
+            created_at: chrono::Utc::now(),
+        };
+        (game, compat, guide)
+    }
+
+    #[test]
+    fn export_slug() {
+        assert_eq!(slug("Genshin Impact"), "genshin-impact");
+        assert_eq!(slug("Half-Life 2: Episode One"), "half-life-2-episode-one");
+        assert_eq!(slug("!!!"), "game");
+    }
+
+    #[test]
+    fn export_box64rc() {
+        let (game, compat, guide) = fixture();
+        let rc = ExportFormat::BOX64RC
+            .render(&game, &compat, Some(&guide))
+            .unwrap();
+        assert_eq!(
+            rc,
+            "# Genshin Impact\n[GenshinImpact.exe]\nBOX64_DYNAREC_BIGBLOCK=0\n"
+        );
+    }
+
+    #[test]
+    fn export_launcher() {
+        let (game, compat, guide) = fixture();
+        let script = ExportFormat::LAUNCHER
+            .render(&game, &compat, Some(&guide))
+            .unwrap();
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("export WINEPREFIX=\"$HOME\"/'.wine-genshin'\n"));
+        assert!(script.contains("export DXVK_HUD='fps'\\''s'\n"));
+        assert!(script.contains("winetricks -q vcrun2019\n"));
+        assert!(script.ends_with("exec box64 wine './GenshinImpact.exe'\n"));
+
+        let native = Compatibility::default();
+        let script = ExportFormat::LAUNCHER.render(&game, &native, None).unwrap();
+        assert!(!script.contains("winetricks"));
+        assert!(script.ends_with("exec './game.exe'\n"));
+    }
+
+    #[test]
+    fn export_escape() {
+        let (mut game, compat, mut guide) = fixture();
+        game.name = "Evil\nrm -rf ~".to_string();
+        guide.command = Some(guide::CommandArgs(vec![
+            "wine".to_string(),
+            "Genshin Impact.exe".to_string(),
+            "; rm -rf ~ $(id)".to_string(),
+        ]));
+        let rc = ExportFormat::BOX64RC
+            .render(&game, &compat, Some(&guide))
+            .unwrap();
+        assert!(rc.starts_with("# Evil rm -rf ~\n[GenshinImpact.exe]\n"));
+        let script = ExportFormat::LAUNCHER
+            .render(&game, &compat, Some(&guide))
+            .unwrap();
+        assert!(script.starts_with("#!/bin/sh\n# Evil rm -rf ~\nset -e\n"));
+        assert!(script.contains("export DXVK_HUD='fps'\\''s'\n"));
+        assert!(script.ends_with("exec 'wine' 'Genshin Impact.exe' '; rm -rf ~ $(id)'\n"));
+    }
+
+    #[test]
+    fn export_lutris() {
+        let (game, compat, guide) = fixture();
+        let yaml = ExportFormat::LUTRIS
+            .render(&game, &compat, Some(&guide))
+            .unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(value["runner"], "wine");
+        assert_eq!(value["game_slug"], "genshin-impact");
+        assert_eq!(value["script"]["game"]["exe"], "$GAMEDIR/GenshinImpact.exe");
+        assert_eq!(value["script"]["installer"][1]["task"]["app"], "vcrun2019");
+        assert_eq!(value["script"]["system"]["prefix_command"], "box64");
+        assert_eq!(
+            value["script"]["system"]["env"]["BOX64_DYNAREC_BIGBLOCK"],
+            "0"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use crate::database::{connect_test, TestDatabase};
+use crate::entity::game;
+use crate::migration::Migrator;
+use sea_orm::{DatabaseConnection, IntoActiveModel};
+use sea_orm_migration::MigratorTrait;
+
+/// 建立测试数据库并执行全部迁移。返回的 [`TestDatabase`] 需保留到测试结束。
+pub async fn setup() -> (DatabaseConnection, TestDatabase) {
+    let (db, guard) = connect_test().await;
+    Migrator::up(&db, None).await.unwrap();
+    (db, guard)
+}
+
+/// 名为 "Test {id}" 的游戏，尚未写入数据库，可用结构体更新语法修改其他字段。
+pub fn game(id: i32) -> game::ActiveModel {
+    game_model(id).into_active_model()
+}
+
+/// 与 [`game`] 相同的游戏模型，用于不需要数据库的测试。
+pub fn game_model(id: i32) -> game::Model {
+    game::Model {
+        name: format!("Test {}", id),
+        id,
+        supportlevel: game::SupportLevel::GOOD,
+        compat: game::Compatibility::default(),
+        confidence: None,
+        developer: None,
+        publisher: None,
+        release_year: None,
+        parent_id: None,
+        parent_kind: None,
+        deleted_at: None,
+        grade_rank: None,
+        best_supportlevel: None,
+        best_confidence: None,
+    }
+}
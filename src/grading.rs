@@ -0,0 +1,292 @@
+use crate::entity::game::{Abi, Compatibility, CompatibilityLayerItem, SupportLevel};
+use config::{Config, ConfigError};
+use lazy_static::lazy_static;
+use log::warn;
+use sea_orm::Iterable;
+use serde::de::{DeserializeOwned, IntoDeserializer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{RwLock, RwLockReadGuard};
+
+lazy_static! {
+    static ref RULES: RwLock<GradingRules> = RwLock::new(load().unwrap_or_else(|e| {
+        warn!("Failed to load grading rules, using defaults: {}", e);
+        GradingRules::default()
+    }));
+}
+
+/// 运行效果的含义，越靠前越好。
+pub const LEVEL_DESCRIPTIONS: [(SupportLevel, &str); 5] = [
+    (SupportLevel::PERFECT, "Runs perfectly."),
+    (SupportLevel::GREAT, "Runs well with minor issues."),
+    (SupportLevel::GOOD, "Playable with noticeable issues."),
+    (SupportLevel::BAD, "Starts but is barely playable."),
+    (SupportLevel::FAIL, "Does not run."),
+];
+
+/// 评级规则，对应设置项 `[GRADING]`。
+///
+/// 评级由字母与其重复次数组成：字母由运行效果决定，重复次数为 `MAX_REPEAT`
+/// 减去所需兼容层与 ABI 的权重，最少为一次。同一分组内的兼容层只计权重最大者，
+/// 未分组的兼容层各自成组。
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GradingRules {
+    pub max_repeat: usize,
+    #[serde(deserialize_with = "variant_keys")]
+    pub level_letters: HashMap<SupportLevel, String>,
+    #[serde(default, deserialize_with = "variant_keys")]
+    pub layer_weights: HashMap<CompatibilityLayerItem, usize>,
+    #[serde(default, deserialize_with = "variant_keys")]
+    pub layer_groups: HashMap<CompatibilityLayerItem, String>,
+    #[serde(default, deserialize_with = "variant_keys")]
+    pub abi_weights: HashMap<Abi, usize>,
+}
+
+/// 以枚举名为键的表。config 读取设置时会将键转为小写，因此按大写后的名称解析。
+fn variant_keys<'de, D, K, V>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    K: DeserializeOwned + Eq + Hash,
+    V: Deserialize<'de>,
+{
+    HashMap::<String, V>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| {
+            let key = K::deserialize(key.to_uppercase().into_deserializer())
+                .map_err(|e: serde::de::value::Error| serde::de::Error::custom(e))?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+impl Default for GradingRules {
+    /// 与 settings.toml 中的默认配置一致。
+    fn default() -> Self {
+        let level_letters = [
+            (SupportLevel::PERFECT, "S"),
+            (SupportLevel::GREAT, "A"),
+            (SupportLevel::GOOD, "B"),
+            (SupportLevel::BAD, "C"),
+            (SupportLevel::FAIL, "D"),
+        ];
+        let layer_groups = [
+            (CompatibilityLayerItem::WINE, "wine"),
+            (CompatibilityLayerItem::LATX, "translator"),
+            (CompatibilityLayerItem::LATA, "translator"),
+            (CompatibilityLayerItem::BOX64, "translator"),
+        ];
+        GradingRules {
+            max_repeat: 3,
+            level_letters: level_letters
+                .into_iter()
+                .map(|(level, letter)| (level, letter.to_string()))
+                .collect(),
+            layer_weights: CompatibilityLayerItem::iter()
+                .map(|item| (item, 1))
+                .collect(),
+            layer_groups: layer_groups
+                .into_iter()
+                .map(|(item, group)| (item, group.to_string()))
+                .collect(),
+            abi_weights: Abi::iter().map(|abi| (abi, 0)).collect(),
+        }
+    }
+}
+
+impl GradingRules {
+    /// 运行效果对应的字母，未配置时为 "?"。
+    pub fn letter(&self, level: &SupportLevel) -> &str {
+        self.level_letters
+            .get(level)
+            .map(|letter| letter.as_str())
+            .unwrap_or("?")
+    }
+
+    /// 所需兼容层与 ABI 的总权重。
+    pub fn penalty(&self, compat: &Compatibility, abi: Option<&Abi>) -> usize {
+        let mut groups: HashMap<String, usize> = HashMap::new();
+        for item in compat.0.iter() {
+            let weight = self.layer_weights.get(&item).copied().unwrap_or(0);
+            let group = self
+                .layer_groups
+                .get(&item)
+                .cloned()
+                .unwrap_or_else(|| format!("{:?}", item));
+            let entry = groups.entry(group).or_default();
+            *entry = (*entry).max(weight);
+        }
+        let abi = abi
+            .and_then(|abi| self.abi_weights.get(abi))
+            .copied()
+            .unwrap_or(0);
+        groups.values().sum::<usize>() + abi
+    }
+
+    /// 字母的重复次数，范围为 1 到 `MAX_REPEAT`。
+    pub fn repeat(&self, compat: &Compatibility, abi: Option<&Abi>) -> usize {
+        self.max_repeat
+            .saturating_sub(self.penalty(compat, abi))
+            .max(1)
+    }
+
+    /// 计算评级，`abi` 未知时不计 ABI 权重。
+    pub fn grade(&self, level: &SupportLevel, compat: &Compatibility, abi: Option<&Abi>) -> String {
+        self.letter(level).repeat(self.repeat(compat, abi))
+    }
+
+    /// 评级的排序键，按从好到差排序；无法识别的评级排在最后。
+    /// 评级去掉末尾重复的字母后须与某个运行效果的字母完全相同，有多个时取最长的字母。
+    pub fn order(&self, grade: &str) -> (usize, std::cmp::Reverse<usize>) {
+        let levels: Vec<SupportLevel> = SupportLevel::iter().collect();
+        levels
+            .iter()
+            .enumerate()
+            .filter_map(|(index, level)| {
+                let letter = self.letter(level);
+                let repeat = repeat_count(grade, letter)?;
+                Some((std::cmp::Reverse(letter.len()), index, repeat))
+            })
+            .min()
+            .map(|(_, index, repeat)| (index, std::cmp::Reverse(repeat)))
+            .unwrap_or((levels.len(), std::cmp::Reverse(grade.chars().count())))
+    }
+
+    /// 各重复次数的含义，次数越多所需的兼容层越少。
+    pub fn repeat_descriptions(&self) -> Vec<(usize, String)> {
+        let max_repeat = self.max_repeat.max(1);
+        (1..=max_repeat)
+            .rev()
+            .map(|repeat| {
+                let penalty = max_repeat - repeat;
+                let description = if penalty == 0 {
+                    "No compatibility layer required.".to_string()
+                } else if repeat == 1 {
+                    format!(
+                        "Compatibility layers weighing {} or more required.",
+                        penalty
+                    )
+                } else {
+                    format!("Compatibility layers weighing {} required.", penalty)
+                };
+                (repeat, description)
+            })
+            .collect()
+    }
+}
+
+/// 从 settings.toml 读取评级规则。
+fn load() -> Result<GradingRules, ConfigError> {
+    Config::builder()
+        .add_source(config::File::with_name("settings.toml"))
+        .build()?
+        .get::<GradingRules>("GRADING")
+}
+
+/// 当前生效的评级规则。
+pub fn rules() -> RwLockReadGuard<'static, GradingRules> {
+    RULES.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 重新读取 settings.toml 中的评级规则，之后计算的评级均使用新规则。
+pub fn reload() -> Result<GradingRules, ConfigError> {
+    let rules = load()?;
+    *RULES.write().unwrap_or_else(|e| e.into_inner()) = rules.clone();
+    Ok(rules)
+}
+
+/// 评级由 `letter` 重复几次组成，不是由它重复组成时返回 `None`。
+fn repeat_count(grade: &str, letter: &str) -> Option<usize> {
+    if letter.is_empty() {
+        return None;
+    }
+    let mut rest = grade;
+    let mut repeat = 1;
+    while let Some(stripped) = rest.strip_suffix(letter).filter(|s| !s.is_empty()) {
+        rest = stripped;
+        repeat += 1;
+    }
+    (rest == letter).then_some(repeat)
+}
+
+#[cfg(test)]
+mod tests {
+    use enumflags2::make_bitflags;
+    use enumflags2::BitFlags;
+    use std::cmp::Reverse;
+    use std::collections::HashMap;
+
+    use crate::entity::game::Abi;
+    use crate::entity::game::Compatibility;
+    use crate::entity::game::CompatibilityLayerItem;
+    use crate::entity::game::SupportLevel;
+
+    use super::load;
+    use super::GradingRules;
+
+    #[test]
+    fn grading_settings_match_default() {
+        assert_eq!(load().unwrap(), GradingRules::default());
+    }
+
+    #[test]
+    fn grading_custom_weights() {
+        let rules = GradingRules {
+            layer_groups: HashMap::new(),
+            abi_weights: HashMap::from([(Abi::LIBLOL, 1)]),
+            ..Default::default()
+        };
+        let wine = Compatibility(make_bitflags!(CompatibilityLayerItem::{WINE}));
+        let stacked = Compatibility(make_bitflags!(CompatibilityLayerItem::{LATX | BOX64}));
+        assert_eq!(rules.grade(&SupportLevel::GREAT, &wine, None), "AA");
+        assert_eq!(
+            rules.grade(&SupportLevel::GREAT, &wine, Some(&Abi::LIBLOL)),
+            "A"
+        );
+        assert_eq!(rules.grade(&SupportLevel::GOOD, &stacked, None), "B");
+        assert_eq!(
+            rules.grade(
+                &SupportLevel::PERFECT,
+                &Compatibility(BitFlags::empty()),
+                Some(&Abi::NEWWORLD)
+            ),
+            "SSS"
+        );
+    }
+
+    #[test]
+    fn grading_order_letters() {
+        let rules = GradingRules::default();
+        assert_eq!(rules.order("AA"), (1, Reverse(2)));
+        assert_eq!(rules.order("SSS"), (0, Reverse(3)));
+        // 混有其他字母的评级无法识别
+        assert_eq!(rules.order("AB"), (5, Reverse(2)));
+
+        // 一个字母是另一个的前缀时不能只比较开头
+        let rules = GradingRules {
+            level_letters: HashMap::from([
+                (SupportLevel::PERFECT, "S".to_string()),
+                (SupportLevel::GREAT, "A".to_string()),
+                (SupportLevel::GOOD, "B".to_string()),
+                (SupportLevel::BAD, "B-".to_string()),
+                (SupportLevel::FAIL, "F".to_string()),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(rules.order("BB"), (2, Reverse(2)));
+        assert_eq!(rules.order("B-B-"), (3, Reverse(2)));
+        assert_eq!(rules.order("B-"), (3, Reverse(1)));
+        assert_eq!(rules.order("B-B"), (5, Reverse(3)));
+    }
+
+    #[test]
+    fn grading_repeat_descriptions() {
+        let rules = GradingRules::default();
+        let repeats: Vec<usize> = rules
+            .repeat_descriptions()
+            .into_iter()
+            .map(|(repeat, _)| repeat)
+            .collect();
+        assert_eq!(repeats, vec![3, 2, 1]);
+    }
+}
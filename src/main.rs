@@ -1,23 +1,54 @@
-use crate::game::Entity;
 use crate::response_body::VersionResponse;
-use log::{debug, error, info, warn};
-use sea_orm::schema;
-use sea_orm::ConnectionTrait;
+use log::{debug, error, warn};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::DatabaseConnection;
+use sea_orm::DbErr;
 mod entity {
+    pub mod alias;
+    pub mod build;
+    pub mod dependency;
+    pub mod distribution;
+    pub mod external_id;
     pub mod game;
+    pub mod game_dependency;
+    pub mod game_layer;
+    pub mod game_stack;
+    pub mod game_tag;
+    pub mod guide;
+    pub mod guide_step;
+    pub mod hardware;
+    pub mod issue;
+    pub mod issue_layer;
+    pub mod layer;
+    pub mod patch;
+    pub mod report;
+    pub mod report_layer;
+    pub mod tag;
+    pub mod tester;
 }
 mod response_body;
 mod response_code;
-use entity::game;
+use entity::{
+    alias, build, dependency, distribution, external_id, game, game_dependency, game_layer,
+    game_stack, game_tag, guide, guide_step, hardware, issue, issue_layer, layer, report,
+    report_layer, tag, tester,
+};
 mod action;
+mod aggregation;
+mod database;
+mod export;
+#[cfg(test)]
+mod fixtures;
+mod grading;
 mod login;
+mod markdown;
+mod migration;
 mod sonic;
 use actix_identity::{Identity, IdentityMiddleware};
 use actix_session::{storage::CookieSessionStore, SessionMiddleware};
-use actix_web::{cookie::Key, get, App, HttpResponse, HttpServer, Responder};
+use actix_web::{cookie::Key, get, web, App, HttpResponse, HttpServer, Responder};
 use config::Config;
 use lazy_static::lazy_static;
-use sea_orm::Database;
 
 lazy_static! {
     static ref settings: Config = Config::builder()
@@ -37,53 +68,77 @@ async fn hello(user: Option<Identity>) -> impl Responder {
 }
 
 #[get("/version")]
-async fn version() -> impl Responder {
+async fn version(db: web::Data<DatabaseConnection>) -> impl Responder {
+    let schema = Migrator::get_applied_migrations(db.get_ref())
+        .await
+        .ok()
+        .and_then(|migrations| migrations.last().map(|m| m.name().to_string()));
     let response = VersionResponse {
         code: 200,
         message: "OK".to_string(),
         version: Some(settings.get_string("VERSION").unwrap()),
+        schema,
     };
     HttpResponse::Ok().json(response)
 }
 
-async fn init() -> Result<(), ()> {
+async fn init() -> Result<DatabaseConnection, ()> {
     env_logger::init();
     debug!("Initializing database...");
-    let db = Database::connect(settings.get_string("DATABASE_URL").unwrap())
+    let db = database::connect(&settings.get_string("DATABASE_URL").unwrap())
         .await
         .unwrap();
-    let builder = db.get_database_backend();
-    let schema = schema::Schema::new(builder);
-    let result = db
-        .execute(builder.build(&schema.create_table_from_entity(Entity)))
-        .await;
-    // dbg!(&result);
-    match result {
-        Ok(_t) => {}
-        Err(e) => {
-            if e.to_string().contains("already exists") {
-                info!("Table already exists, skipping table creation...");
-            } else {
-                error!("Failed to create table!");
-                dbg!(e);
-                return Err(());
-            }
-        }
-    };
     debug!("Testing SonicDB connection...");
     if !sonic::sonic_connection_test() {
         warn!("SonicDB connection test failed, skipping...");
     }
-    Ok(())
+    Ok(db)
+}
+
+/// 处理 `migrate` 子命令：`up`（默认）执行全部待执行的迁移，`down` 回滚最近一次迁移，
+/// `status` 列出各迁移是否已执行。
+async fn migrate(db: &DatabaseConnection, command: Option<&str>) -> Result<(), DbErr> {
+    match command.unwrap_or("up") {
+        "up" => Migrator::up(db, None).await,
+        "down" => Migrator::down(db, Some(1)).await,
+        "status" => {
+            for migration in Migrator::get_migration_with_status(db).await? {
+                println!("{}\t{}", migration.status(), migration.name());
+            }
+            Ok(())
+        }
+        command => Err(DbErr::Custom(format!(
+            "Unknown migrate command: {}",
+            command
+        ))),
+    }
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    init().await.unwrap();
+    let db = init().await.unwrap();
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("migrate") {
+        // 只处理迁移，不启动服务
+        return migrate(&db, args.get(2).map(String::as_str))
+            .await
+            .map_err(std::io::Error::other);
+    }
+    if let Err(e) = Migrator::up(&db, None).await {
+        error!("Failed to run database migrations: {}", e);
+        return Err(std::io::Error::other(e));
+    }
+    // 补齐迁移前已有游戏的评级排序键
+    if let Err(e) = aggregation::regrade_all(&db, true).await {
+        error!("Failed to compute grades: {}", e);
+        return Err(std::io::Error::other(e));
+    }
+    let db = web::Data::new(db);
     let secret = settings.get_string("ACTIX_SECRET").unwrap();
     let secret = Key::from(secret.as_bytes());
     HttpServer::new(move || {
         App::new()
+            .app_data(db.clone())
             .wrap(IdentityMiddleware::default())
             .wrap(SessionMiddleware::new(
                 CookieSessionStore::default(),
@@ -95,9 +150,53 @@ async fn main() -> std::io::Result<()> {
             .service(login::oauth_callback)
             .service(login::logout)
             .service(action::add)
+            .service(action::update)
             .service(action::info)
+            .service(action::info_by_external)
             .service(action::search)
+            .service(action::game_list)
+            .service(action::grades)
+            .service(action::grades_reload)
+            .service(action::aggregation_reload)
             .service(action::delete)
+            .service(action::restore)
+            .service(action::purge)
+            .service(action::report_add)
+            .service(action::report_list)
+            .service(action::report_delete)
+            .service(action::tester_reputation)
+            .service(action::hardware_add)
+            .service(action::hardware_info)
+            .service(action::hardware_list)
+            .service(action::hardware_update)
+            .service(action::hardware_delete)
+            .service(action::distribution_add)
+            .service(action::distribution_list)
+            .service(action::layer_add)
+            .service(action::layer_list)
+            .service(action::layer_link)
+            .service(action::layer_unlink)
+            .service(action::alias_add)
+            .service(action::alias_delete)
+            .service(action::external_add)
+            .service(action::external_delete)
+            .service(action::tag_add)
+            .service(action::tag_list)
+            .service(action::tag_link)
+            .service(action::tag_unlink)
+            .service(action::guide_list)
+            .service(action::guide_add)
+            .service(action::guide_delete)
+            .service(action::export_game)
+            .service(action::issue_add)
+            .service(action::issue_list)
+            .service(action::issue_fix)
+            .service(action::dependency_add)
+            .service(action::dependency_list)
+            .service(action::dependency_link)
+            .service(action::dependency_unlink)
+            .service(action::build_add)
+            .service(action::build_list)
     })
     .bind(("127.0.0.1", 8080))?
     .run()
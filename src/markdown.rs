@@ -0,0 +1,51 @@
+use pulldown_cmark::{html, Event, Parser};
+
+/// 去掉 Markdown 中的原始 HTML，其余内容原样保留。
+pub fn sanitize(markdown: &str) -> String {
+    let mut removed: Vec<std::ops::Range<usize>> = Parser::new(markdown)
+        .into_offset_iter()
+        .filter(|(event, _)| matches!(event, Event::Html(_) | Event::InlineHtml(_)))
+        .map(|(_, range)| range)
+        .collect();
+    removed.sort_by_key(|range| range.start);
+    let mut result = String::with_capacity(markdown.len());
+    let mut position = 0;
+    for range in removed {
+        if range.start >= position {
+            result.push_str(&markdown[position..range.start]);
+        }
+        position = position.max(range.end);
+    }
+    result.push_str(&markdown[position..]);
+    result.trim().to_string()
+}
+
+/// 将 Markdown 渲染为 HTML，并清理其中的脚本、事件属性与危险链接。
+pub fn render(markdown: &str) -> String {
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, Parser::new(markdown));
+    ammonia::clean(&rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use super::sanitize;
+
+    #[test]
+    fn markdown_sanitize() {
+        let markdown = "Install **wine** first.\n\n<script>alert(1)</script>\n\nRun `a && b` <img src=x onerror=alert(1)> then.";
+        let sanitized = sanitize(markdown);
+        assert!(!sanitized.contains("<script>"));
+        assert!(!sanitized.contains("<img"));
+        assert!(sanitized.starts_with("Install **wine** first."));
+        assert!(sanitized.contains("`a && b`"));
+    }
+
+    #[test]
+    fn markdown_render() {
+        let html = render("[link](javascript:alert(1)) and *text*");
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("<em>text</em>"));
+    }
+}
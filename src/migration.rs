@@ -0,0 +1,291 @@
+pub use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::{ColumnDef, ForeignKeyCreateStatement};
+
+mod m20250101_000001_create_games;
+mod m20250101_000002_add_game_metadata;
+mod m20250101_000003_add_game_deleted_at;
+mod m20250101_000004_add_game_confidence;
+mod m20250101_000005_add_game_parent;
+mod m20250101_000006_create_hardware;
+mod m20250101_000007_create_distributions;
+mod m20250101_000008_create_layers;
+mod m20250101_000009_create_tags;
+mod m20250101_000010_create_testers;
+mod m20250101_000011_create_dependencies;
+mod m20250101_000012_create_builds;
+mod m20250101_000013_create_reports;
+mod m20250101_000014_create_report_layers;
+mod m20250101_000015_create_game_layers;
+mod m20250101_000016_create_aliases;
+mod m20250101_000017_create_external_ids;
+mod m20250101_000018_create_game_tags;
+mod m20250101_000019_create_game_stacks;
+mod m20250101_000020_create_guides;
+mod m20250101_000021_create_guide_steps;
+mod m20250101_000022_create_issues;
+mod m20250101_000023_create_issue_layers;
+mod m20250101_000024_create_game_dependencies;
+mod m20250201_000001_game_id_sequence;
+mod m20250301_000001_add_game_grade_rank;
+mod m20250301_000002_add_hardware_owner;
+mod m20250301_000003_guide_command_args;
+mod m20250301_000004_add_game_best_stack;
+
+/// 按顺序执行的数据库迁移，已执行的迁移记录在 `seaql_migrations` 表中。
+/// 修改实体的字段时，应新增一个迁移，而不是修改已有的迁移。
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20250101_000001_create_games::Migration),
+            Box::new(m20250101_000002_add_game_metadata::Migration),
+            Box::new(m20250101_000003_add_game_deleted_at::Migration),
+            Box::new(m20250101_000004_add_game_confidence::Migration),
+            Box::new(m20250101_000005_add_game_parent::Migration),
+            Box::new(m20250101_000006_create_hardware::Migration),
+            Box::new(m20250101_000007_create_distributions::Migration),
+            Box::new(m20250101_000008_create_layers::Migration),
+            Box::new(m20250101_000009_create_tags::Migration),
+            Box::new(m20250101_000010_create_testers::Migration),
+            Box::new(m20250101_000011_create_dependencies::Migration),
+            Box::new(m20250101_000012_create_builds::Migration),
+            Box::new(m20250101_000013_create_reports::Migration),
+            Box::new(m20250101_000014_create_report_layers::Migration),
+            Box::new(m20250101_000015_create_game_layers::Migration),
+            Box::new(m20250101_000016_create_aliases::Migration),
+            Box::new(m20250101_000017_create_external_ids::Migration),
+            Box::new(m20250101_000018_create_game_tags::Migration),
+            Box::new(m20250101_000019_create_game_stacks::Migration),
+            Box::new(m20250101_000020_create_guides::Migration),
+            Box::new(m20250101_000021_create_guide_steps::Migration),
+            Box::new(m20250101_000022_create_issues::Migration),
+            Box::new(m20250101_000023_create_issue_layers::Migration),
+            Box::new(m20250101_000024_create_game_dependencies::Migration),
+            Box::new(m20250201_000001_game_id_sequence::Migration),
+            Box::new(m20250301_000001_add_game_grade_rank::Migration),
+            Box::new(m20250301_000002_add_hardware_owner::Migration),
+            Box::new(m20250301_000003_guide_command_args::Migration),
+            Box::new(m20250301_000004_add_game_best_stack::Migration),
+        ]
+    }
+}
+
+/// 为已有的表添加一列。SQLite 每条语句只能修改一列，因此逐列执行。
+async fn add_column<T: IntoIden + 'static>(
+    manager: &SchemaManager<'_>,
+    table: T,
+    mut column: ColumnDef,
+) -> Result<(), DbErr> {
+    manager
+        .alter_table(
+            Table::alter()
+                .table(table)
+                .add_column(&mut column)
+                .to_owned(),
+        )
+        .await
+}
+
+async fn drop_column<T: IntoIden + 'static, C: IntoIden>(
+    manager: &SchemaManager<'_>,
+    table: T,
+    column: C,
+) -> Result<(), DbErr> {
+    manager
+        .alter_table(Table::alter().table(table).drop_column(column).to_owned())
+        .await
+}
+
+/// 删除被引用的行时一并删除。
+fn cascade<T: IntoIden + 'static, C: IntoIden, R: IntoIden + 'static, RC: IntoIden>(
+    table: T,
+    column: C,
+    ref_table: R,
+    ref_column: RC,
+) -> ForeignKeyCreateStatement {
+    ForeignKey::create()
+        .from(table, column)
+        .to(ref_table, ref_column)
+        .on_delete(ForeignKeyAction::Cascade)
+        .to_owned()
+}
+
+/// 删除被引用的行时将本列置空。
+fn set_null<T: IntoIden + 'static, C: IntoIden, R: IntoIden + 'static, RC: IntoIden>(
+    table: T,
+    column: C,
+    ref_table: R,
+    ref_column: RC,
+) -> ForeignKeyCreateStatement {
+    ForeignKey::create()
+        .from(table, column)
+        .to(ref_table, ref_column)
+        .on_delete(ForeignKeyAction::SetNull)
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::{
+        alias, build, dependency, distribution, external_id, game, game_dependency, game_layer,
+        game_stack, game_tag, guide, guide_step, hardware, issue, issue_layer, layer, report,
+        report_layer, tag, tester,
+    };
+    use sea_orm::{schema, ActiveModelTrait, ActiveValue, ConnectionTrait, EntityTrait};
+    use sea_orm_migration::MigratorTrait;
+
+    use super::Migrator;
+
+    /// 使用迁移之前的 `games` 表，启动时由 `create_table_from_entity` 建出。
+    mod legacy {
+        use crate::entity::game::{Compatibility, SupportLevel};
+        use sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+        #[sea_orm(table_name = "games")]
+        pub struct Model {
+            pub name: String,
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub supportlevel: SupportLevel,
+            pub compat: Compatibility,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    /// 逐个查询实体的全部列，任一列缺失都会报错。
+    macro_rules! assert_columns {
+        ($db:expr, $($entity:ident),*) => {
+            $(
+                $entity::Entity::find().one($db).await.unwrap();
+            )*
+        };
+    }
+
+    #[tokio::test]
+    async fn migration_matches_entities() {
+        let (db, _guard) = crate::database::connect_test().await;
+        Migrator::up(&db, None).await.unwrap();
+        assert_columns!(
+            &db,
+            game,
+            hardware,
+            distribution,
+            build,
+            report,
+            layer,
+            game_layer,
+            report_layer,
+            alias,
+            external_id,
+            tag,
+            game_tag,
+            tester,
+            game_stack,
+            guide,
+            guide_step,
+            issue,
+            issue_layer,
+            dependency,
+            game_dependency
+        );
+        assert!(Migrator::get_pending_migrations(&db)
+            .await
+            .unwrap()
+            .is_empty());
+        // 已执行的迁移不会重复执行
+        Migrator::up(&db, None).await.unwrap();
+
+        Migrator::down(&db, None).await.unwrap();
+        assert!(game::Entity::find().one(&db).await.is_err());
+        assert_eq!(
+            Migrator::get_pending_migrations(&db).await.unwrap().len(),
+            Migrator::migrations().len()
+        );
+    }
+
+    #[tokio::test]
+    async fn migration_upgrades_legacy_games() {
+        let (db, _guard) = crate::database::connect_test().await;
+        let builder = db.get_database_backend();
+        let schema = schema::Schema::new(builder);
+        db.execute(builder.build(&schema.create_table_from_entity(legacy::Entity)))
+            .await
+            .unwrap();
+        let old = legacy::ActiveModel {
+            name: ActiveValue::Set("Test 1".to_string()),
+            id: ActiveValue::Set(1),
+            supportlevel: ActiveValue::Set(game::SupportLevel::GOOD),
+            compat: ActiveValue::Set(game::Compatibility::default()),
+        };
+        old.insert(&db).await.unwrap();
+
+        Migrator::up(&db, None).await.unwrap();
+        assert_columns!(
+            &db,
+            game,
+            hardware,
+            distribution,
+            build,
+            report,
+            layer,
+            game_layer,
+            report_layer,
+            alias,
+            external_id,
+            tag,
+            game_tag,
+            tester,
+            game_stack,
+            guide,
+            guide_step,
+            issue,
+            issue_layer,
+            dependency,
+            game_dependency
+        );
+        let visible = game::Entity::find_visible().all(&db).await.unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].name, "Test 1");
+        assert_eq!(visible[0].parent_id, None);
+    }
+
+    #[tokio::test]
+    async fn migration_assigns_game_ids() {
+        let (db, _guard) = crate::database::connect_test().await;
+        let steps = Migrator::migrations()
+            .iter()
+            .position(|migration| migration.name() == "m20250201_000001_game_id_sequence")
+            .unwrap() as u32;
+        Migrator::up(&db, Some(steps)).await.unwrap();
+        // 旧版本由客户端指定 id
+        let legacy = game::ActiveModel {
+            name: ActiveValue::Set("Test 5".to_string()),
+            id: ActiveValue::Set(5),
+            supportlevel: ActiveValue::Set(game::SupportLevel::GOOD),
+            compat: ActiveValue::Set(game::Compatibility::default()),
+            ..Default::default()
+        };
+        // 此时还没有之后迁移添加的列，只写入已设置的字段
+        game::Entity::insert(legacy)
+            .exec_without_returning(&db)
+            .await
+            .unwrap();
+        Migrator::up(&db, None).await.unwrap();
+
+        let game = game::ActiveModel {
+            name: ActiveValue::Set("Test 6".to_string()),
+            supportlevel: ActiveValue::Set(game::SupportLevel::GOOD),
+            compat: ActiveValue::Set(game::Compatibility::default()),
+            ..Default::default()
+        };
+        let game = game.insert(&db).await.unwrap();
+        assert_eq!(game.id, 6);
+    }
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 使用迁移之前的表结构，与当时 `create_table_from_entity` 建出的表一致。
+/// 旧部署中表已存在，因此使用 `IF NOT EXISTS`，之后的列由后续迁移添加。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Games::Table)
+                    .if_not_exists()
+                    .col(string(Games::Name))
+                    .col(integer(Games::Id).auto_increment().primary_key())
+                    .col(integer(Games::Supportlevel))
+                    .col(integer(Games::Compat))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Games::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Name,
+    Id,
+    Supportlevel,
+    Compat,
+}
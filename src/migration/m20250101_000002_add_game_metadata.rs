@@ -0,0 +1,35 @@
+use super::{add_column, drop_column};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏的开发商、发行商与发行年份。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_column(manager, Games::Table, string_null(Games::Developer)).await?;
+        add_column(manager, Games::Table, string_null(Games::Publisher)).await?;
+        add_column(
+            manager,
+            Games::Table,
+            small_integer_null(Games::ReleaseYear),
+        )
+        .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Games::Table, Games::ReleaseYear).await?;
+        drop_column(manager, Games::Table, Games::Publisher).await?;
+        drop_column(manager, Games::Table, Games::Developer).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Developer,
+    Publisher,
+    ReleaseYear,
+}
@@ -0,0 +1,29 @@
+use super::{add_column, drop_column};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏的软删除时间。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_column(
+            manager,
+            Games::Table,
+            timestamp_with_time_zone_null(Games::DeletedAt),
+        )
+        .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Games::Table, Games::DeletedAt).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    DeletedAt,
+}
@@ -0,0 +1,24 @@
+use super::{add_column, drop_column};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 由报告汇总得出的置信度。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_column(manager, Games::Table, double_null(Games::Confidence)).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Games::Table, Games::Confidence).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Confidence,
+}
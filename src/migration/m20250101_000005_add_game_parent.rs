@@ -0,0 +1,53 @@
+use super::{add_column, drop_column};
+use sea_orm::DatabaseBackend;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 上级游戏及关系种类。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_column(manager, Games::Table, integer_null(Games::ParentId)).await?;
+        add_column(manager, Games::Table, integer_null(Games::ParentKind)).await?;
+        // SQLite 不能为已有的表添加外键，删除上级时由 /purge 负责清空
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            manager
+                .create_foreign_key(
+                    ForeignKey::create()
+                        .name("fk_games_parent_id")
+                        .from(Games::Table, Games::ParentId)
+                        .to(Games::Table, Games::Id)
+                        .on_delete(ForeignKeyAction::SetNull)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            manager
+                .drop_foreign_key(
+                    ForeignKey::drop()
+                        .name("fk_games_parent_id")
+                        .table(Games::Table)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        drop_column(manager, Games::Table, Games::ParentKind).await?;
+        drop_column(manager, Games::Table, Games::ParentId).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+    ParentId,
+    ParentKind,
+}
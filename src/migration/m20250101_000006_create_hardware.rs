@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 硬件登记表。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Hardware::Table)
+                    .col(integer(Hardware::Id).auto_increment().primary_key())
+                    .col(string(Hardware::Cpu))
+                    .col(string_null(Hardware::Board))
+                    .col(integer_null(Hardware::Ram))
+                    .col(string_null(Hardware::Gpu))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Hardware::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Hardware {
+    Table,
+    Id,
+    Cpu,
+    Board,
+    Ram,
+    Gpu,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 发行版登记表。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Distributions::Table)
+                    .col(integer(Distributions::Id).auto_increment().primary_key())
+                    .col(string(Distributions::Name))
+                    .col(string_null(Distributions::Version))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Distributions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Distributions {
+    Table,
+    Id,
+    Name,
+    Version,
+}
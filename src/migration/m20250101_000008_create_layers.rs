@@ -0,0 +1,38 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 兼容层登记表。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Layers::Table)
+                    .col(integer(Layers::Id).auto_increment().primary_key())
+                    .col(string(Layers::Name))
+                    .col(integer(Layers::Kind))
+                    .col(string_null(Layers::Version))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Layers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Layers {
+    Table,
+    Id,
+    Name,
+    Kind,
+    Version,
+}
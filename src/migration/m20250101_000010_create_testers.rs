@@ -0,0 +1,34 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 测试者及其信誉。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Testers::Table)
+                    .col(string(Testers::Name).primary_key())
+                    .col(double(Testers::Reputation))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Testers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Testers {
+    Table,
+    Name,
+    Reputation,
+}
@@ -0,0 +1,38 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 运行库目录。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Dependencies::Table)
+                    .col(integer(Dependencies::Id).auto_increment().primary_key())
+                    .col(string_uniq(Dependencies::Name))
+                    .col(integer(Dependencies::Kind))
+                    .col(string_null(Dependencies::Winetricks))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Dependencies::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Dependencies {
+    Table,
+    Id,
+    Name,
+    Kind,
+    Winetricks,
+}
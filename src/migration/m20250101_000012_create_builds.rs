@@ -0,0 +1,59 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏版本。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Builds::Table)
+                    .col(integer(Builds::Id).auto_increment().primary_key())
+                    .col(integer(Builds::Gameid))
+                    .col(string(Builds::Version))
+                    .col(string_null(Builds::Edition))
+                    .col(date_null(Builds::ReleasedOn))
+                    .col(string_null(Builds::ExeSha256))
+                    .col(integer_null(Builds::Supportlevel))
+                    .col(double_null(Builds::Confidence))
+                    .foreign_key(&mut cascade(
+                        Builds::Table,
+                        Builds::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Builds::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Builds {
+    Table,
+    Id,
+    Gameid,
+    Version,
+    Edition,
+    ReleasedOn,
+    ExeSha256,
+    Supportlevel,
+    Confidence,
+}
@@ -0,0 +1,103 @@
+use super::{cascade, set_null};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 兼容性报告。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Reports::Table)
+                    .col(integer(Reports::Id).auto_increment().primary_key())
+                    .col(integer(Reports::Gameid))
+                    .col(string(Reports::Tester))
+                    .col(integer(Reports::Supportlevel))
+                    .col(integer(Reports::Compat))
+                    .col(integer_null(Reports::Hardwareid))
+                    .col(integer_null(Reports::Distributionid))
+                    .col(integer_null(Reports::Buildid))
+                    .col(integer_null(Reports::Abi))
+                    .col(string_null(Reports::Kernel))
+                    .col(string_null(Reports::Comment))
+                    .col(timestamp_with_time_zone(Reports::CreatedAt))
+                    .foreign_key(&mut cascade(
+                        Reports::Table,
+                        Reports::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .foreign_key(&mut set_null(
+                        Reports::Table,
+                        Reports::Hardwareid,
+                        Hardware::Table,
+                        Hardware::Id,
+                    ))
+                    .foreign_key(&mut set_null(
+                        Reports::Table,
+                        Reports::Distributionid,
+                        Distributions::Table,
+                        Distributions::Id,
+                    ))
+                    .foreign_key(&mut set_null(
+                        Reports::Table,
+                        Reports::Buildid,
+                        Builds::Table,
+                        Builds::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Reports::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Hardware {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Distributions {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Builds {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Reports {
+    Table,
+    Id,
+    Gameid,
+    Tester,
+    Supportlevel,
+    Compat,
+    Hardwareid,
+    Distributionid,
+    Buildid,
+    Abi,
+    Kernel,
+    Comment,
+    CreatedAt,
+}
@@ -0,0 +1,64 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 报告使用的兼容层。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReportLayers::Table)
+                    .col(integer(ReportLayers::Reportid))
+                    .col(integer(ReportLayers::Layerid))
+                    .primary_key(
+                        Index::create()
+                            .col(ReportLayers::Reportid)
+                            .col(ReportLayers::Layerid),
+                    )
+                    .foreign_key(&mut cascade(
+                        ReportLayers::Table,
+                        ReportLayers::Reportid,
+                        Reports::Table,
+                        Reports::Id,
+                    ))
+                    .foreign_key(&mut cascade(
+                        ReportLayers::Table,
+                        ReportLayers::Layerid,
+                        Layers::Table,
+                        Layers::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReportLayers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Layers {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Reports {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ReportLayers {
+    Table,
+    Reportid,
+    Layerid,
+}
@@ -0,0 +1,66 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏关联的兼容层及其作用。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameLayers::Table)
+                    .col(integer(GameLayers::Gameid))
+                    .col(integer(GameLayers::Layerid))
+                    .col(integer(GameLayers::Role))
+                    .primary_key(
+                        Index::create()
+                            .col(GameLayers::Gameid)
+                            .col(GameLayers::Layerid),
+                    )
+                    .foreign_key(&mut cascade(
+                        GameLayers::Table,
+                        GameLayers::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .foreign_key(&mut cascade(
+                        GameLayers::Table,
+                        GameLayers::Layerid,
+                        Layers::Table,
+                        Layers::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameLayers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Layers {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum GameLayers {
+    Table,
+    Gameid,
+    Layerid,
+    Role,
+}
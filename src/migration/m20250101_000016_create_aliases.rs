@@ -0,0 +1,53 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏的别名。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Aliases::Table)
+                    .col(integer(Aliases::Id).auto_increment().primary_key())
+                    .col(integer(Aliases::Gameid))
+                    .col(string(Aliases::Name))
+                    .col(string_null(Aliases::Language))
+                    .col(boolean(Aliases::IsPrimary))
+                    .foreign_key(&mut cascade(
+                        Aliases::Table,
+                        Aliases::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Aliases::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Aliases {
+    Table,
+    Id,
+    Gameid,
+    Name,
+    Language,
+    IsPrimary,
+}
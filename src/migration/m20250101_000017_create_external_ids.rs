@@ -0,0 +1,54 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏在各商店的 id。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExternalIds::Table)
+                    .col(string_len(ExternalIds::Store, 16))
+                    .col(string(ExternalIds::ExternalId))
+                    .col(integer(ExternalIds::Gameid))
+                    .primary_key(
+                        Index::create()
+                            .col(ExternalIds::Store)
+                            .col(ExternalIds::ExternalId),
+                    )
+                    .foreign_key(&mut cascade(
+                        ExternalIds::Table,
+                        ExternalIds::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExternalIds::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ExternalIds {
+    Table,
+    Store,
+    ExternalId,
+    Gameid,
+}
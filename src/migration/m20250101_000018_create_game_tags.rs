@@ -0,0 +1,60 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏与标签的关联。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameTags::Table)
+                    .col(integer(GameTags::Gameid))
+                    .col(integer(GameTags::Tagid))
+                    .primary_key(Index::create().col(GameTags::Gameid).col(GameTags::Tagid))
+                    .foreign_key(&mut cascade(
+                        GameTags::Table,
+                        GameTags::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .foreign_key(&mut cascade(
+                        GameTags::Table,
+                        GameTags::Tagid,
+                        Tags::Table,
+                        Tags::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameTags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum GameTags {
+    Table,
+    Gameid,
+    Tagid,
+}
@@ -0,0 +1,59 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 各兼容层组合下的运行效果。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameStacks::Table)
+                    .col(integer(GameStacks::Gameid))
+                    .col(string(GameStacks::Layers))
+                    .col(integer(GameStacks::Compat))
+                    .col(integer(GameStacks::Supportlevel))
+                    .col(double(GameStacks::Confidence))
+                    .primary_key(
+                        Index::create()
+                            .col(GameStacks::Gameid)
+                            .col(GameStacks::Layers)
+                            .col(GameStacks::Compat),
+                    )
+                    .foreign_key(&mut cascade(
+                        GameStacks::Table,
+                        GameStacks::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameStacks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum GameStacks {
+    Table,
+    Gameid,
+    Layers,
+    Compat,
+    Supportlevel,
+    Confidence,
+}
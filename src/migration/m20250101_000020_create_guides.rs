@@ -0,0 +1,77 @@
+use super::{cascade, set_null};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 配置指南。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Guides::Table)
+                    .col(integer(Guides::Id).auto_increment().primary_key())
+                    .col(integer(Guides::Gameid))
+                    .col(integer_null(Guides::Reportid))
+                    .col(string(Guides::Author))
+                    .col(string(Guides::Title))
+                    .col(string_null(Guides::WinePrefix))
+                    .col(json(Guides::Env))
+                    .col(json(Guides::Winetricks))
+                    .col(string_null(Guides::Executable))
+                    .col(string_null(Guides::Command))
+                    .col(timestamp_with_time_zone(Guides::CreatedAt))
+                    .foreign_key(&mut cascade(
+                        Guides::Table,
+                        Guides::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .foreign_key(&mut set_null(
+                        Guides::Table,
+                        Guides::Reportid,
+                        Reports::Table,
+                        Reports::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Guides::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Reports {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Guides {
+    Table,
+    Id,
+    Gameid,
+    Reportid,
+    Author,
+    Title,
+    WinePrefix,
+    Env,
+    Winetricks,
+    Executable,
+    Command,
+    CreatedAt,
+}
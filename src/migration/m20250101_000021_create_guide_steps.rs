@@ -0,0 +1,56 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 指南的步骤。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GuideSteps::Table)
+                    .col(integer(GuideSteps::Guideid))
+                    .col(integer(GuideSteps::Position))
+                    .col(string(GuideSteps::Body))
+                    .col(string(GuideSteps::BodyHtml))
+                    .primary_key(
+                        Index::create()
+                            .col(GuideSteps::Guideid)
+                            .col(GuideSteps::Position),
+                    )
+                    .foreign_key(&mut cascade(
+                        GuideSteps::Table,
+                        GuideSteps::Guideid,
+                        Guides::Table,
+                        Guides::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GuideSteps::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Guides {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum GuideSteps {
+    Table,
+    Guideid,
+    Position,
+    Body,
+    BodyHtml,
+}
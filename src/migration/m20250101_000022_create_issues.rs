@@ -0,0 +1,61 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 已知问题。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Issues::Table)
+                    .col(integer(Issues::Id).auto_increment().primary_key())
+                    .col(integer(Issues::Gameid))
+                    .col(string(Issues::Reporter))
+                    .col(string(Issues::Title))
+                    .col(integer(Issues::Severity))
+                    .col(string_null(Issues::Workaround))
+                    .col(string_null(Issues::BugUrl))
+                    .col(string_null(Issues::FixedIn))
+                    .col(timestamp_with_time_zone(Issues::CreatedAt))
+                    .foreign_key(&mut cascade(
+                        Issues::Table,
+                        Issues::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Issues::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Issues {
+    Table,
+    Id,
+    Gameid,
+    Reporter,
+    Title,
+    Severity,
+    Workaround,
+    BugUrl,
+    FixedIn,
+    CreatedAt,
+}
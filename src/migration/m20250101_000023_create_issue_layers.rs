@@ -0,0 +1,64 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 问题影响的兼容层版本。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(IssueLayers::Table)
+                    .col(integer(IssueLayers::Issueid))
+                    .col(integer(IssueLayers::Layerid))
+                    .primary_key(
+                        Index::create()
+                            .col(IssueLayers::Issueid)
+                            .col(IssueLayers::Layerid),
+                    )
+                    .foreign_key(&mut cascade(
+                        IssueLayers::Table,
+                        IssueLayers::Issueid,
+                        Issues::Table,
+                        Issues::Id,
+                    ))
+                    .foreign_key(&mut cascade(
+                        IssueLayers::Table,
+                        IssueLayers::Layerid,
+                        Layers::Table,
+                        Layers::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IssueLayers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Layers {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Issues {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum IssueLayers {
+    Table,
+    Issueid,
+    Layerid,
+}
@@ -0,0 +1,66 @@
+use super::cascade;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 游戏所需的运行库。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameDependencies::Table)
+                    .col(integer(GameDependencies::Gameid))
+                    .col(integer(GameDependencies::Dependencyid))
+                    .col(string_null(GameDependencies::Version))
+                    .primary_key(
+                        Index::create()
+                            .col(GameDependencies::Gameid)
+                            .col(GameDependencies::Dependencyid),
+                    )
+                    .foreign_key(&mut cascade(
+                        GameDependencies::Table,
+                        GameDependencies::Gameid,
+                        Games::Table,
+                        Games::Id,
+                    ))
+                    .foreign_key(&mut cascade(
+                        GameDependencies::Table,
+                        GameDependencies::Dependencyid,
+                        Dependencies::Table,
+                        Dependencies::Id,
+                    ))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameDependencies::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Dependencies {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum GameDependencies {
+    Table,
+    Gameid,
+    Dependencyid,
+    Version,
+}
@@ -0,0 +1,28 @@
+use sea_orm::DatabaseBackend;
+use sea_orm_migration::prelude::*;
+
+/// 游戏 id 改由数据库分配。此前的 id 由客户端指定，PostgreSQL 的序列不会随之前进，
+/// 需要将其移到已有最大 id 之后；SQLite 与 MySQL 会自动从最大 id 继续分配。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Postgres {
+            return Ok(());
+        }
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "SELECT setval(pg_get_serial_sequence('games', 'id'), \
+                 COALESCE(MAX(id), 0) + 1, false) FROM games",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
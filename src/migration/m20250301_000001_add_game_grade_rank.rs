@@ -0,0 +1,24 @@
+use super::{add_column, drop_column};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 评级的排序键。已有的游戏在服务启动时补齐。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_column(manager, Games::Table, integer_null(Games::GradeRank)).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Games::Table, Games::GradeRank).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    GradeRank,
+}
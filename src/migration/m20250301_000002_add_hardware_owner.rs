@@ -0,0 +1,24 @@
+use super::{add_column, drop_column};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 登记机器的用户。已有的机器没有登记者，只有管理员可以修改。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_column(manager, Hardware::Table, string_null(Hardware::Owner)).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Hardware::Table, Hardware::Owner).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Hardware {
+    Table,
+    Owner,
+}
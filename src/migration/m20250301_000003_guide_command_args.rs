@@ -0,0 +1,26 @@
+use super::{add_column, drop_column};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 指南的启动命令改为参数列表。原有的命令行无法可靠地拆分为参数，迁移时清空。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Guides::Table, Guides::Command).await?;
+        add_column(manager, Guides::Table, json_null(Guides::Command)).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Guides::Table, Guides::Command).await?;
+        add_column(manager, Guides::Table, string_null(Guides::Command)).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Guides {
+    Table,
+    Command,
+}
@@ -0,0 +1,28 @@
+use super::{add_column, drop_column};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+/// 评级最好的兼容层组合的运行效果与置信度，与全部报告的汇总结果分开保存。
+/// 已有的游戏在重新汇总后补齐。
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_column(manager, Games::Table, integer_null(Games::BestSupportlevel)).await?;
+        add_column(manager, Games::Table, double_null(Games::BestConfidence)).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_column(manager, Games::Table, Games::BestConfidence).await?;
+        drop_column(manager, Games::Table, Games::BestSupportlevel).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    BestSupportlevel,
+    BestConfidence,
+}
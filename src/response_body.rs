@@ -1,4 +1,19 @@
+use super::entity::alias;
+use super::entity::build;
+use super::entity::dependency;
+use super::entity::distribution;
+use super::entity::external_id;
 use super::entity::game;
+use super::entity::game_layer;
+use super::entity::game_stack;
+use super::entity::guide;
+use super::entity::guide_step;
+use super::entity::hardware;
+use super::entity::issue;
+use super::entity::layer;
+use super::entity::report;
+use super::entity::tag;
+use super::grading::GradingRules;
 use serde::Serialize;
 
 /// 对于大多数请求的基本响应。
@@ -8,6 +23,14 @@ pub struct BasicResponse<'a> {
     pub message: &'a str,
 }
 
+/// 新增游戏的响应，附带数据库分配的 id。
+#[derive(Serialize)]
+pub struct AddResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub game: Option<game::Model>,
+}
+
 /// 对于 OAuth 登录的响应。
 #[derive(Serialize)]
 pub struct AuthUrlResponse<'a> {
@@ -16,11 +39,34 @@ pub struct AuthUrlResponse<'a> {
     pub url: Option<&'a str>,
 }
 
+/// 游戏列表中的一项，附带标签。
+#[derive(Serialize)]
+pub struct GameSummary {
+    #[serde(flatten)]
+    pub game: game::Model,
+    pub grade: String,
+    pub tags: Vec<tag::Model>,
+    /// 折叠到本体下的 DLC，仅在搜索时要求折叠才会出现。
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dlcs: Vec<game::Model>,
+}
+
 #[derive(Serialize)]
 pub struct SearchResponse<'a> {
     pub code: u32,
     pub message: &'a str,
-    pub games: Vec<game::Model>,
+    pub games: Vec<GameSummary>,
+}
+
+/// 分页浏览游戏的响应，`total` 为符合条件的游戏总数。
+#[derive(Serialize)]
+pub struct GameListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub total: u64,
+    pub offset: u64,
+    pub limit: u64,
+    pub games: Vec<GameSummary>,
 }
 
 #[derive(Serialize)]
@@ -28,6 +74,225 @@ pub struct InfoResponse<'a> {
     pub code: u32,
     pub message: &'a str,
     pub game: Option<game::Model>,
+    pub grade: Option<String>,
+    /// 上级游戏，如 DLC 所属的本体。
+    pub parent: Option<game::Model>,
+    /// DLC、资料片、重制版等下级游戏。
+    pub children: Vec<game::Model>,
+    /// 各兼容层组合下的运行效果。
+    pub stacks: Vec<StackDetail>,
+    pub aliases: Vec<alias::Model>,
+    pub layers: Vec<GameLayerDetail>,
+    pub external_ids: Vec<external_id::Model>,
+    pub tags: Vec<tag::Model>,
+    /// 尚未解决的已知问题。
+    pub issues: Vec<IssueDetail>,
+    pub dependencies: Vec<GameDependencyDetail>,
+    /// 已登记的游戏版本，最新的在前。
+    pub builds: Vec<BuildDetail>,
+}
+
+/// 游戏版本及其是否可用。
+#[derive(Serialize)]
+pub struct BuildDetail {
+    #[serde(flatten)]
+    pub build: build::Model,
+    pub known_good: bool,
+}
+
+/// 游戏关联的兼容层及其作用。
+#[derive(Serialize)]
+pub struct GameLayerDetail {
+    #[serde(flatten)]
+    pub layer: layer::Model,
+    pub role: game_layer::LayerRole,
+}
+
+/// 游戏所需的运行库及版本要求。
+#[derive(Serialize)]
+pub struct GameDependencyDetail {
+    #[serde(flatten)]
+    pub dependency: dependency::Model,
+    pub version: Option<String>,
+}
+
+/// 某一兼容层组合的运行效果及评级。
+#[derive(Serialize)]
+pub struct StackDetail {
+    #[serde(flatten)]
+    pub stack: game_stack::Model,
+    pub grade: String,
+}
+
+/// 报告及其使用的兼容层。
+#[derive(Serialize)]
+pub struct ReportDetail {
+    #[serde(flatten)]
+    pub report: report::Model,
+    pub layers: Vec<layer::Model>,
+    /// 按报告自身的运行效果、兼容层与 ABI 计算的评级。
+    pub grade: String,
+}
+
+/// 某个游戏的全部兼容性报告。
+#[derive(Serialize)]
+pub struct ReportListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub reports: Vec<ReportDetail>,
+}
+
+#[derive(Serialize)]
+pub struct HardwareInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub hardware: Option<hardware::Model>,
+}
+
+#[derive(Serialize)]
+pub struct HardwareListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub hardware: Vec<hardware::Model>,
+}
+
+#[derive(Serialize)]
+pub struct DistributionInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub distribution: Option<distribution::Model>,
+}
+
+#[derive(Serialize)]
+pub struct DistributionListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub distributions: Vec<distribution::Model>,
+}
+
+#[derive(Serialize)]
+pub struct LayerInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub layer: Option<layer::Model>,
+}
+
+#[derive(Serialize)]
+pub struct LayerListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub layers: Vec<layer::Model>,
+}
+
+#[derive(Serialize)]
+pub struct BuildInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub build: Option<build::Model>,
+}
+
+#[derive(Serialize)]
+pub struct BuildListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub builds: Vec<BuildDetail>,
+}
+
+#[derive(Serialize)]
+pub struct DependencyInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub dependency: Option<dependency::Model>,
+}
+
+#[derive(Serialize)]
+pub struct DependencyListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub dependencies: Vec<dependency::Model>,
+}
+
+#[derive(Serialize)]
+pub struct TagInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub tag: Option<tag::Model>,
+}
+
+#[derive(Serialize)]
+pub struct TagListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub tags: Vec<tag::Model>,
+}
+
+/// 评级字母的含义。
+#[derive(Serialize)]
+pub struct GradeLetter {
+    pub letter: String,
+    pub supportlevel: game::SupportLevel,
+    pub description: &'static str,
+}
+
+/// 评级字母重复次数的含义。
+#[derive(Serialize)]
+pub struct GradeRepeat {
+    pub repeat: usize,
+    pub description: String,
+}
+
+#[derive(Serialize)]
+pub struct GradeLegendResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub letters: Vec<GradeLetter>,
+    pub repeats: Vec<GradeRepeat>,
+    /// 当前生效的评级规则，包括各兼容层与 ABI 的权重。
+    pub rules: GradingRules,
+}
+
+/// 指南及其按顺序排列的步骤。
+#[derive(Debug, Serialize)]
+pub struct GuideDetail {
+    #[serde(flatten)]
+    pub guide: guide::Model,
+    pub steps: Vec<guide_step::Model>,
+}
+
+#[derive(Serialize)]
+pub struct GuideInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub guide: Option<GuideDetail>,
+}
+
+#[derive(Serialize)]
+pub struct GuideListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub guides: Vec<GuideDetail>,
+}
+
+/// 已知问题及受影响的兼容层版本。
+#[derive(Debug, Serialize)]
+pub struct IssueDetail {
+    #[serde(flatten)]
+    pub issue: issue::Model,
+    pub layers: Vec<layer::Model>,
+}
+
+#[derive(Serialize)]
+pub struct IssueInfoResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub issue: Option<IssueDetail>,
+}
+
+#[derive(Serialize)]
+pub struct IssueListResponse<'a> {
+    pub code: u32,
+    pub message: &'a str,
+    pub issues: Vec<IssueDetail>,
 }
 
 #[derive(Serialize)]
@@ -35,4 +300,6 @@ pub struct VersionResponse {
     pub code: u32,
     pub message: String,
     pub version: Option<String>,
+    /// 最近一次执行的数据库迁移。
+    pub schema: Option<String>,
 }
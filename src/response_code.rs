@@ -1,9 +1,26 @@
 pub enum ResponseCode {
     Success = 0,
+    #[allow(dead_code)]
     NotImplemented = 998,
     SystemInternalError = 999,
+    InvalidRequest = 1000,
     LoginCsrfViolation = 1001,
+    LoginRequired = 1002,
+    PermissionDenied = 1003,
     DatabaseConnectionError = 2001,
+    GameNotFound = 2002,
+    HardwareNotFound = 2003,
+    DistributionNotFound = 2004,
+    LayerNotFound = 2005,
+    AliasNotFound = 2006,
+    ExternalIDNotFound = 2007,
+    ExternalIDConflict = 2008,
+    TagNotFound = 2009,
+    ReportNotFound = 2010,
+    GuideNotFound = 2011,
+    IssueNotFound = 2012,
+    DependencyNotFound = 2013,
+    BuildNotFound = 2014,
     SonicDBConnectionError = 3001,
 }
 
@@ -1,9 +1,12 @@
+use super::alias;
 use super::game;
 use config;
 use config::Config;
 use lazy_static::lazy_static;
 use sonic_channel2::Dest;
+use sonic_channel2::FlushRequest;
 use sonic_channel2::IngestChannel;
+use sonic_channel2::Lang;
 use sonic_channel2::PushRequest;
 use sonic_channel2::QueryRequest;
 use sonic_channel2::SearchChannel;
@@ -26,7 +29,18 @@ pub fn sonic_connection_test() -> bool {
     channel.is_ok()
 }
 
-pub fn sonic_write_game(game: game::Model) -> Result<(), String> {
+/// 别名的语言代码对应的 Sonic 分词语言，未知语言交给 Sonic 自动识别。
+fn sonic_lang(language: &str) -> Option<Lang> {
+    match language {
+        "zh" => Some(Lang::Cmn),
+        "en" => Some(Lang::Eng),
+        "ja" => Some(Lang::Jpn),
+        code => Lang::from_code(code),
+    }
+}
+
+/// 将游戏名及其全部别名写入 Sonic，写入前会清空该游戏原有的索引。
+pub fn sonic_write_game(game: game::Model, aliases: Vec<alias::Model>) -> Result<(), String> {
     // PERFORMANCE: 用r2d2重写该部分以加快效率
     let channel = IngestChannel::start(
         settings.get_string("SONICDB_URL").unwrap(),
@@ -35,16 +49,48 @@ pub fn sonic_write_game(game: game::Model) -> Result<(), String> {
     .unwrap();
 
     let dest = Dest::col_buc("loonggamedb", "games").obj(game.id);
-    let pushed = channel.push(PushRequest::new(dest, game.name));
+    let flushed = channel.flush(FlushRequest::from(dest.clone()));
+    if flushed.is_err() {
+        return Err(flushed.err().unwrap().to_string());
+    }
+    let pushed = channel.push(PushRequest::new(dest.clone(), game.name));
     dbg!(&pushed);
     if pushed.is_err() {
         return Err(pushed.err().unwrap().to_string());
     }
+    for alias in aliases {
+        let mut request = PushRequest::new(dest.clone(), alias.name);
+        if let Some(lang) = alias.language.as_deref().and_then(sonic_lang) {
+            request = request.lang(lang);
+        }
+        let pushed = channel.push(request);
+        if pushed.is_err() {
+            return Err(pushed.err().unwrap().to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// 从 Sonic 中移除游戏的全部索引。
+pub fn sonic_delete_game(id: i32) -> Result<(), String> {
+    // PERFORMANCE: 用r2d2重写该部分以加快效率
+    let channel = IngestChannel::start(
+        settings.get_string("SONICDB_URL").unwrap(),
+        settings.get_string("SONICDB_PASSWORD").unwrap(),
+    )
+    .unwrap();
+
+    let dest = Dest::col_buc("loonggamedb", "games").obj(id);
+    let flushed = channel.flush(FlushRequest::from(dest));
+    if flushed.is_err() {
+        return Err(flushed.err().unwrap().to_string());
+    }
 
     Ok(())
 }
 
-pub fn sonic_read_game(name: String) -> Result<Vec<u32>, String> {
+pub fn sonic_read_game(name: String) -> Result<Vec<i32>, String> {
     // PERFORMANCE: 用r2d2重写该部分以加快效率
     let channel = SearchChannel::start(
         settings.get_string("SONICDB_URL").unwrap(),
@@ -61,7 +107,7 @@ pub fn sonic_read_game(name: String) -> Result<Vec<u32>, String> {
     match game {
         Ok(candidates) => Ok(candidates
             .iter()
-            .map(|i| i.parse::<u32>().ok().unwrap())
+            .map(|i| i.parse::<i32>().ok().unwrap())
             .collect()),
         Err(error) => Err("Failed while reading from sonic:".to_owned() + &error.to_string()),
     }
@@ -69,6 +115,7 @@ pub fn sonic_read_game(name: String) -> Result<Vec<u32>, String> {
 
 #[cfg(test)]
 mod tests {
+    use crate::fixtures;
     use enumflags2::make_bitflags;
     use game::CompatibilityLayerItem;
     // use sea_orm::IntoActiveModel;
@@ -89,8 +136,9 @@ mod tests {
             compat: game::Compatibility {
                 0: make_bitflags!(CompatibilityLayerItem::{LATX}),
             },
+            ..fixtures::game_model(1)
         };
-        sonic_write_game(game).unwrap();
+        sonic_write_game(game, Vec::new()).unwrap();
     }
 
     #[test]
@@ -102,8 +150,9 @@ mod tests {
             compat: game::Compatibility {
                 0: make_bitflags!(CompatibilityLayerItem::{LATX}),
             },
+            ..fixtures::game_model(1)
         };
-        sonic_write_game(game).unwrap();
+        sonic_write_game(game, Vec::new()).unwrap();
         let games = sonic_read_game("Test Music 001".to_owned()).unwrap();
         dbg!(&games);
         assert_eq!(games.len(), 1);